@@ -0,0 +1,59 @@
+use crate::object::Object;
+use crate::raw_value::RawValue;
+
+/// A newly created bookmark, see [Space::create_bookmark](crate::Space::create_bookmark)
+#[derive(Debug, Clone)]
+pub struct CreatedBookmark {
+    pub object: Object,
+    /// Why the url's preview couldn't be fetched, in which case the bookmark was created with
+    /// only its url
+    pub preview_error: Option<tonic::Status>,
+}
+
+/// A bookmark object with typed access to the details anytype-heart fills in from the page it
+/// points at, see [Object::as_bookmark]
+#[derive(Debug, Clone)]
+pub struct Bookmark(Object);
+
+impl Bookmark {
+    pub(crate) fn new(object: Object) -> Self {
+        Self(object)
+    }
+
+    /// The bookmark as a plain object
+    pub fn object(&self) -> &Object {
+        &self.0
+    }
+
+    /// The url the bookmark points at
+    pub fn url(&self) -> Option<String> {
+        self.string_detail("source")
+    }
+
+    /// The title of the page, which is the bookmark object's name
+    pub fn title(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.string_detail("description")
+    }
+
+    /// The id of the file object holding the page's favicon
+    pub fn favicon(&self) -> Option<String> {
+        self.string_detail("iconImage")
+    }
+
+    /// The id of the file object holding the page's preview image
+    pub fn picture(&self) -> Option<String> {
+        self.string_detail("picture")
+    }
+
+    fn string_detail(&self, key: &str) -> Option<String> {
+        // AnyType stores missing strings as empty ones
+        match self.0.get_raw(key) {
+            Some(RawValue::String(string)) if !string.is_empty() => Some(string),
+            _ => None,
+        }
+    }
+}
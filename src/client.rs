@@ -1,15 +1,34 @@
+use std::fmt::Display;
+use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+#[cfg(feature = "background-tasks")]
+use std::time::Duration;
 
+use crate::object::{Object, ObjectId, ObjectLayout};
 use crate::pb::{self, client_commands_client::ClientCommandsClient, models::Account};
-use crate::request::RequestWithToken;
+use crate::prost_ext::{IntoProstValue, ProstConversionError, ProstStruct, TryFromProst};
+use crate::request::{self, RequestWithToken};
+use crate::response::check_response_error;
 use crate::space::{Space, SpaceInner};
 
 #[derive(Debug)]
 pub(crate) struct ClientInner {
     pub(crate) grpc: ClientCommandsClient<tonic::transport::Channel>,
     pub(crate) token: String,
+    pub(crate) network_sync: NetworkSync,
+    /// Whether Url, Email and Phone values are checked to look like one before being sent
+    pub(crate) check_well_formed_values: bool,
+    /// How many requests a single batch operation keeps in flight at once
+    pub(crate) max_concurrent_requests: usize,
+    /// anytype-heart's session events besides the ones consumed while authenticating, only
+    /// available when the event listener is spawned
+    pub(crate) events: Option<tokio::sync::broadcast::Sender<pb::event::message::Value>>,
+    /// Whether to shut anytype-heart down once the client is dropped, unset once the account was
+    /// logged out or anytype-heart was already shut down explicitly
+    shutdown_on_drop: AtomicBool,
 }
 
 #[derive(Debug, Clone)]
@@ -25,51 +44,105 @@ impl Deref for Client {
     }
 }
 
+impl ClientInner {
+    /// Send a request made with the session token, see [request::send]
+    pub(crate) async fn request<T, R, F, Fut>(&self, request: T, rpc: F) -> Result<R, tonic::Status>
+    where
+        F: FnOnce(ClientCommandsClient<tonic::transport::Channel>, tonic::Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        request::send(&self.grpc, &self.token, request, rpc).await
+    }
+
+    /// Open the space with the given id, returning its info or `None` if there's no such space
+    pub(crate) async fn workspace_open(
+        &self,
+        space_id: &str,
+    ) -> Result<Option<pb::models::account::Info>, tonic::Status> {
+        let response = self
+            .request(
+                pb::rpc::workspace::open::Request {
+                    space_id: space_id.to_string(),
+                },
+                |mut grpc, request| async move { grpc.workspace_open(request).await },
+            )
+            .await?;
+
+        if let Err(status) = check_response_error(response.error) {
+            // TODO: This hack will hopefully not last forever, currently anytype-heart doesn't
+            // really give any better way of detecting an incorrect space_id error though
+            if status.code() == tonic::Code::Unknown
+                && status.message()
+                    == "failed to get derived ids: failed to get space: space not exists"
+            {
+                return Ok(None);
+            }
+
+            return Err(status);
+        }
+
+        match response.info {
+            Some(info) => Ok(Some(info)),
+            None => Err(tonic::Status::internal(
+                "anytype-heart did not respond with a space's info",
+            )),
+        }
+    }
+
+    async fn app_shutdown(
+        grpc: ClientCommandsClient<tonic::transport::Channel>,
+        token: &str,
+    ) -> Result<(), tonic::Status> {
+        // TODO: This doesn't actually do much right now. Ask AnyType team for a graceful
+        // way of shutting down
+        let response = request::send(
+            &grpc,
+            token,
+            pb::rpc::app::shutdown::Request {},
+            |mut grpc, request| async move { grpc.app_shutdown(request).await },
+        )
+        .await?;
+
+        check_response_error(response.error)
+    }
+}
+
+/// Shutting down on drop needs a background task, without one anytype-heart can only be shut down
+/// with [AuthorizedAnytypeClient::shutdown]
+#[cfg(feature = "background-tasks")]
 impl Drop for ClientInner {
     fn drop(&mut self) {
-        let mut grpc = self.grpc.clone();
+        if !self.shutdown_on_drop.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let grpc = self.grpc.clone();
         let token = self.token.clone();
 
         tokio::spawn(async move {
-            // TODO: This doesn't actually do much right now. Ask AnyType team for a graceful
-            // way of shutting down
-            let response = grpc
-                .app_shutdown(RequestWithToken {
-                    request: pb::rpc::app::shutdown::Request {},
-                    token: &token,
-                })
-                .await;
-
-            let response = match response {
-                Ok(response) => response,
-                Err(error) => {
-                    // TODO: Proper logging
-                    dbg!(error);
-                    return;
-                }
-            };
-
-            if let Some(error) = response.into_inner().error {
-                use pb::rpc::app::shutdown::response::error::Code;
-
-                if matches!(error.code(), Code::Null) {
-                    return;
-                }
-
-                // TODO: Proper logging
-                dbg!(error.description);
+            if let Err(error) = ClientInner::app_shutdown(grpc, &token).await {
+                tracing::warn!(%error, "failed to shut down anytype-heart");
             }
         });
     }
 }
 
+#[derive(Clone)]
 pub struct AnytypeClient {
     inner: ClientCommandsClient<tonic::transport::Channel>,
+    network_sync: NetworkSync,
     disable_local_network_sync: bool,
     network_mode: i32,
+    prefer_yamux_transport: bool,
     root_path: Option<PathBuf>,
+    #[cfg_attr(not(feature = "background-tasks"), allow(dead_code))]
+    spawn_event_listener: bool,
+    shutdown_on_drop: bool,
+    check_well_formed_values: bool,
+    max_concurrent_requests: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NetworkSync {
     Sync,
     LocalOnly,
@@ -79,52 +152,267 @@ pub enum NetworkSync {
 pub struct AuthorizedAnytypeClient {
     client: Client,
     account: Account,
-    event_listener: tokio::sync::mpsc::Receiver<pb::event::message::Value>,
-    event_listener_task: tokio::task::JoinHandle<()>,
+    event_listener: Option<tokio::sync::mpsc::Receiver<pb::event::message::Value>>,
+    event_listener_task: Option<EventListenerTask>,
+    /// The client this one was authorized from, handed back on logout
+    unauthenticated: AnytypeClient,
+}
+
+/// Error returned when the root path anytype-heart should store its data in isn't valid UTF-8,
+/// which anytype-heart requires
+#[derive(Debug)]
+pub struct RootPathNotUtf8(pub PathBuf);
+
+impl Display for RootPathNotUtf8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Root path {} is not valid UTF-8", self.0.display())
+    }
+}
+
+impl std::error::Error for RootPathNotUtf8 {}
+
+impl From<RootPathNotUtf8> for tonic::Status {
+    fn from(value: RootPathNotUtf8) -> Self {
+        tonic::Status::invalid_argument(format!("{value}"))
+    }
+}
+
+/// Error returned when the root path anytype-heart should store its data in can't be created or
+/// written to
+#[derive(Debug)]
+pub struct RootPathNotWritable {
+    pub path: PathBuf,
+    pub error: std::io::Error,
+}
+
+impl Display for RootPathNotWritable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Can't write to root path {}: {}",
+            self.path.display(),
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for RootPathNotWritable {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<RootPathNotWritable> for tonic::Status {
+    fn from(value: RootPathNotWritable) -> Self {
+        tonic::Status::failed_precondition(format!("{value}"))
+    }
+}
+
+/// Error returned when authenticating or creating an account fails
+///
+/// The failures a caller can act on get their own variant, everything else is passed through as
+/// [AuthError::Transport]
+#[derive(Debug)]
+pub enum AuthError {
+    /// The mnemonic isn't a valid recovery phrase
+    InvalidMnemonic,
+    /// anytype-heart couldn't create its local repository in the root path
+    RepoCreationFailed(String),
+    /// The account was recovered before the wallet it belongs to
+    NeedWalletRecoveryFirst,
+    /// anytype-heart didn't accept the session token passed to
+    /// [AnytypeClient::authenticate_with_token]
+    InvalidToken,
+    /// Any other failure, either of the connection itself or reported by anytype-heart
+    Transport(tonic::Status),
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::InvalidMnemonic => {
+                f.write_str("The mnemonic is not a valid recovery phrase")
+            }
+            AuthError::RepoCreationFailed(reason) => {
+                write!(f, "Failed to create the local repository: {reason}")
+            }
+            AuthError::NeedWalletRecoveryFirst => {
+                f.write_str("The wallet has to be recovered before the account")
+            }
+            AuthError::InvalidToken => f.write_str("The session token is invalid or has expired"),
+            AuthError::Transport(status) => write!(f, "{}", status.message()),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AuthError::Transport(status) => Some(status),
+            _ => None,
+        }
+    }
+}
+
+impl From<tonic::Status> for AuthError {
+    fn from(value: tonic::Status) -> Self {
+        AuthError::Transport(value)
+    }
+}
+
+impl From<AuthError> for tonic::Status {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InvalidMnemonic => tonic::Status::invalid_argument(format!("{value}")),
+            AuthError::RepoCreationFailed(_) => tonic::Status::internal(format!("{value}")),
+            AuthError::NeedWalletRecoveryFirst => {
+                tonic::Status::failed_precondition(format!("{value}"))
+            }
+            AuthError::InvalidToken => TokenExpired.into(),
+            AuthError::Transport(status) => status,
+        }
+    }
+}
+
+/// Error returned when anytype-heart stops accepting the session token of an authorized client,
+/// after which every request made with it fails the same way
+///
+/// Recover by authenticating again with [AnytypeClient::authenticate]. Since the error is passed
+/// around as a [tonic::Status] use [TokenExpired::is_cause_of] to tell it apart from other
+/// failures
+#[derive(Debug)]
+pub struct TokenExpired;
+
+impl TokenExpired {
+    /// Whether `status` was caused by anytype-heart rejecting the session token
+    pub fn is_cause_of(status: &tonic::Status) -> bool {
+        std::error::Error::source(status)
+            .is_some_and(|source| source.downcast_ref::<TokenExpired>().is_some())
+    }
+}
+
+impl Display for TokenExpired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("The session token is no longer accepted, authenticate again to continue")
+    }
+}
+
+impl std::error::Error for TokenExpired {}
+
+impl From<TokenExpired> for tonic::Status {
+    fn from(value: TokenExpired) -> Self {
+        let mut status = tonic::Status::unauthenticated(format!("{value}"));
+        status.set_source(Arc::new(value));
+        status
+    }
 }
 
 const MACOS_PATH: &str = "Library/Application Support/anytype/";
 
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// The queue of events consumed while authenticating, the sender the rest of the events are
+/// broadcast on and the task listening to them
+type EventListener = (
+    tokio::sync::mpsc::Receiver<pb::event::message::Value>,
+    tokio::sync::broadcast::Sender<pb::event::message::Value>,
+    EventListenerTask,
+);
+
+/// The task listening to anytype-heart's events, aborted once dropped so that it stops with the
+/// client it was started for, or right away if authenticating fails after it was started
+#[cfg_attr(not(feature = "background-tasks"), allow(dead_code))]
+struct EventListenerTask(tokio::task::JoinHandle<()>);
+
+impl Drop for EventListenerTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// How long to wait before resubscribing to anytype-heart's events after the stream fails, doubled
+/// on every consecutive failure up to [EVENT_STREAM_MAX_BACKOFF]. Failures caused by the session
+/// token being rejected aren't retried
+#[cfg(feature = "background-tasks")]
+const EVENT_STREAM_MIN_BACKOFF: Duration = Duration::from_millis(100);
+#[cfg(feature = "background-tasks")]
+const EVENT_STREAM_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
 impl AnytypeClient {
     pub async fn connect(url: &str) -> Result<Self, tonic::transport::Error> {
-        use pb::rpc::account::NetworkMode;
         use std::str::FromStr;
 
         let url = tonic::transport::Endpoint::from_str(url)?;
         let mut client = ClientCommandsClient::connect(url).await?;
 
-        let response = client
-            .app_get_version(pb::rpc::app::get_version::Request {})
-            .await
-            .expect("app_get_version request to succeed")
-            .into_inner();
+        if let Err(status) = Self::check_version(&mut client).await {
+            panic!("{}", status.message());
+        }
 
-        if let Some(error) = response.error {
-            use pb::rpc::app::get_version::response::error::Code;
+        Ok(Self::new(client))
+    }
 
-            if error.code() != Code::Null {
-                panic!(
-                    "Failed to get anytype-heart server details: {}",
-                    error.description
-                );
-            }
-        };
+    /// Connect over a channel built by the caller, for example to customize TLS or keepalive
+    ///
+    /// Unlike [AnytypeClient::connect] an unsupported anytype-heart version is returned as an
+    /// error instead of panicking
+    pub async fn from_channel(channel: tonic::transport::Channel) -> Result<Self, tonic::Status> {
+        let mut client = ClientCommandsClient::new(channel);
+
+        Self::check_version(&mut client).await?;
 
-        assert!(
-            response.version == "v0.34.0",
-            "anytype-friend currently only supports anytype-heart v0.34.0"
-        );
-        assert!(
-            response.details == "build on 2024-06-07 12:47:15 +0000 UTC at #7a0f64abeaface1cd02a50b8e49549b9ef1097d0",
-            "anytype-friend currently only supports anytype-heart build 7a0f64abeaface1cd02a50b8e49549b9ef1097d0"
-        );
+        Ok(Self::new(client))
+    }
+
+    fn new(client: ClientCommandsClient<tonic::transport::Channel>) -> Self {
+        use pb::rpc::account::NetworkMode;
 
-        Ok(Self {
+        Self {
             inner: client,
+            network_sync: NetworkSync::Sync,
             disable_local_network_sync: false,
             network_mode: NetworkMode::DefaultConfig.into(),
+            prefer_yamux_transport: false,
             root_path: None,
-        })
+            spawn_event_listener: true,
+            shutdown_on_drop: true,
+            check_well_formed_values: false,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+        }
+    }
+
+    async fn check_version(
+        client: &mut ClientCommandsClient<tonic::transport::Channel>,
+    ) -> Result<(), tonic::Status> {
+        let response = client
+            .app_get_version(pb::rpc::app::get_version::Request {})
+            .await?
+            .into_inner();
+
+        if let Err(status) = check_response_error(response.error) {
+            return Err(tonic::Status::new(
+                status.code(),
+                format!(
+                    "Failed to get anytype-heart server details: {}",
+                    status.message()
+                ),
+            ));
+        };
+
+        if response.version != "v0.34.0" {
+            return Err(tonic::Status::failed_precondition(
+                "anytype-friend currently only supports anytype-heart v0.34.0",
+            ));
+        }
+        if response.details
+            != "build on 2024-06-07 12:47:15 +0000 UTC at #7a0f64abeaface1cd02a50b8e49549b9ef1097d0"
+        {
+            return Err(tonic::Status::failed_precondition(
+                "anytype-friend currently only supports anytype-heart build 7a0f64abeaface1cd02a50b8e49549b9ef1097d0",
+            ));
+        }
+
+        Ok(())
     }
 
     fn calculate_root_path(&self) -> Result<PathBuf, tonic::Status> {
@@ -139,69 +427,136 @@ impl AnytypeClient {
         Ok(home_dir.join(MACOS_PATH))
     }
 
+    /// Check that the root path is valid UTF-8 and that it exists or can be created and is
+    /// writable
+    ///
+    /// This is done before creating or authenticating an account anyway, but can be called
+    /// earlier to report a bad root path as soon as it's configured
+    pub fn validate_root_path(&self) -> Result<(), tonic::Status> {
+        self.root_path_string().map(|_| ())
+    }
+
+    fn ensure_writable(path: &Path) -> Result<(), RootPathNotWritable> {
+        let probe = path.join(".anytype-friend-write-check");
+
+        std::fs::create_dir_all(path)
+            .and_then(|_| std::fs::write(&probe, []))
+            .and_then(|_| std::fs::remove_file(&probe))
+            .map_err(|error| RootPathNotWritable {
+                path: path.to_path_buf(),
+                error,
+            })
+    }
+
+    fn root_path_string(&self) -> Result<String, tonic::Status> {
+        let root_path = self.calculate_root_path()?;
+        Self::ensure_writable(&root_path)?;
+
+        let root_path = root_path
+            .into_os_string()
+            .into_string()
+            .map_err(|path| RootPathNotUtf8(PathBuf::from(path)))?;
+
+        Ok(root_path)
+    }
+
     pub async fn authenticate(
         mut self,
         mnemonic: &str,
-    ) -> Result<AuthorizedAnytypeClient, tonic::Status> {
-        let root_path = self
-            .calculate_root_path()?
-            .into_os_string()
-            .into_string()
-            .expect("non utf-8 path root_path");
+    ) -> Result<AuthorizedAnytypeClient, AuthError> {
+        let root_path = self.root_path_string()?;
+
+        self.recover_wallet(&root_path, mnemonic).await?;
+        let token = self.create_wallet_session(mnemonic).await?;
+
+        self.authenticate_session(root_path, token).await
+    }
 
+    async fn recover_wallet(&mut self, root_path: &str, mnemonic: &str) -> Result<(), AuthError> {
         let response = self
             .inner
             .wallet_recover(pb::rpc::wallet::recover::Request {
-                root_path: root_path.clone(),
+                root_path: root_path.to_string(),
                 mnemonic: mnemonic.to_string(),
             })
             .await?
             .into_inner();
 
-        if let Some(error) = response.error {
+        if let Some(error) = &response.error {
             use pb::rpc::wallet::recover::response::error::Code;
             match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
+                Code::BadInput => return Err(AuthError::InvalidMnemonic),
                 Code::FailedToCreateLocalRepo => {
-                    return Err(tonic::Status::internal(error.description))
+                    return Err(AuthError::RepoCreationFailed(error.description.clone()))
                 }
+                _ => {}
             }
-        };
+        }
+        check_response_error(response.error)?;
 
-        let token = self.create_wallet_session(mnemonic).await?;
+        Ok(())
+    }
 
-        let (mut event_listener, event_listener_task) = self.start_event_listener(&token);
+    /// Authenticate with a session token from [AuthorizedAnytypeClient::token] instead of the
+    /// mnemonic, so that long running services don't have to keep the mnemonic around
+    ///
+    /// Session tokens only live as long as the anytype-heart process that issued them or until
+    /// the account is logged out, a token it doesn't accept fails with [AuthError::InvalidToken]
+    pub async fn authenticate_with_token(
+        self,
+        token: &str,
+    ) -> Result<AuthorizedAnytypeClient, AuthError> {
+        let root_path = self.root_path_string()?;
 
-        let response = self
-            .inner
-            .account_recover(RequestWithToken {
-                request: pb::rpc::account::recover::Request {},
-                token: &token,
-            })
-            .await?
-            .into_inner();
+        self.authenticate_session(root_path, token.to_string())
+            .await
+    }
 
-        if let Some(error) = response.error {
-            use pb::rpc::account::recover::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-                Code::NeedToRecoverWalletFirst => {
-                    return Err(tonic::Status::failed_precondition(error.description))
-                }
-            }
-        }
+    async fn authenticate_session(
+        mut self,
+        root_path: String,
+        token: String,
+    ) -> Result<AuthorizedAnytypeClient, AuthError> {
+        let (event_listener, events, event_listener_task, account_id) =
+            if let Some((mut event_listener, events, event_listener_task)) =
+                self.maybe_start_event_listener(&token)
+            {
+                self.recover_account(&token).await?;
+
+                let account_id = Self::wait_account_id_event(&mut event_listener).await;
+                (
+                    Some(event_listener),
+                    Some(events),
+                    Some(event_listener_task),
+                    account_id,
+                )
+            } else {
+                // The stream has to be opened before recovering the account so that the AccountShow
+                // event isn't missed
+                let mut stream = self
+                    .inner
+                    .clone()
+                    .listen_session_events(pb::StreamRequest {
+                        token: token.clone(),
+                    })
+                    .await
+                    .map_err(Self::session_error)?
+                    .into_inner();
+
+                self.recover_account(&token).await?;
+
+                let account_id = Self::wait_account_id_in_stream(&mut stream).await?;
+                (None, None, None, account_id)
+            };
 
-        let Some(account_id) = Self::wait_account_id_event(&mut event_listener).await else {
+        let Some(account_id) = account_id else {
             return Err(tonic::Status::internal(
                 "AnytypeClient internal event queue was unexpectedly closed",
-            ));
+            )
+            .into());
         };
 
-        self.set_metrics().await?;
+        self.set_metrics().await;
 
         let response = self
             .inner
@@ -211,35 +566,69 @@ impl AnytypeClient {
                 disable_local_network_sync: self.disable_local_network_sync,
                 network_mode: self.network_mode,
                 network_custom_config_file_path: "".to_string(),
-                prefer_yamux_transport: false,
+                prefer_yamux_transport: self.prefer_yamux_transport,
             })
             .await?
             .into_inner();
 
-        if let Some(error) = response.error {
-            use pb::rpc::account::select::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-                _ => todo!("So many unique error types..."),
-            }
-        }
+        check_response_error(response.error)?;
 
         Ok(AuthorizedAnytypeClient {
             client: Client {
                 inner: Arc::new(ClientInner {
-                    grpc: self.inner,
+                    grpc: self.inner.clone(),
                     token,
+                    network_sync: self.network_sync,
+                    check_well_formed_values: self.check_well_formed_values,
+                    max_concurrent_requests: self.max_concurrent_requests,
+                    events,
+                    shutdown_on_drop: AtomicBool::new(self.shutdown_on_drop),
                 }),
             },
             account: Self::account_or_error(response.account)?,
             event_listener,
             event_listener_task,
+            unauthenticated: self,
         })
     }
 
-    async fn create_wallet_session(&self, mnemonic: &str) -> Result<String, tonic::Status> {
+    async fn recover_account(&mut self, token: &str) -> Result<(), AuthError> {
+        let response = match self
+            .inner
+            .account_recover(RequestWithToken {
+                request: pb::rpc::account::recover::Request {},
+                token,
+            })
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            Err(status) => return Err(Self::session_error(status)),
+        };
+
+        if let Some(error) = &response.error {
+            use pb::rpc::account::recover::response::error::Code;
+            if error.code() == Code::NeedToRecoverWalletFirst {
+                return Err(AuthError::NeedWalletRecoveryFirst);
+            }
+        }
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    /// Map a failed request made with a session token that wasn't checked yet
+    fn session_error(status: tonic::Status) -> AuthError {
+        match status.code() {
+            // The session was never issued by this anytype-heart or was closed since, account
+            // recover responds to the latter with FailedPrecondition
+            tonic::Code::Unauthenticated | tonic::Code::FailedPrecondition => {
+                AuthError::InvalidToken
+            }
+            _ => status.into(),
+        }
+    }
+
+    async fn create_wallet_session(&self, mnemonic: &str) -> Result<String, AuthError> {
         let response = self
             .inner
             .clone()
@@ -251,91 +640,125 @@ impl AnytypeClient {
             .await?
             .into_inner();
 
-        if let Some(error) = response.error {
+        if let Some(error) = &response.error {
             use pb::rpc::wallet::create_session::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-                Code::AppTokenNotFoundInTheCurrentAccount => {
-                    return Err(tonic::Status::failed_precondition(error.description))
-                }
+            if error.code() == Code::BadInput {
+                return Err(AuthError::InvalidMnemonic);
             }
         }
+        check_response_error(response.error)?;
 
         Ok(response.token)
     }
 
-    fn start_event_listener(
-        &self,
-        token: &str,
-    ) -> (
-        tokio::sync::mpsc::Receiver<pb::event::message::Value>,
-        tokio::task::JoinHandle<()>,
-    ) {
+    #[cfg(feature = "background-tasks")]
+    fn maybe_start_event_listener(&self, token: &str) -> Option<EventListener> {
+        self.spawn_event_listener
+            .then(|| self.start_event_listener(token))
+    }
+
+    /// Without background tasks events are only ever awaited inline
+    #[cfg(not(feature = "background-tasks"))]
+    fn maybe_start_event_listener(&self, _token: &str) -> Option<EventListener> {
+        None
+    }
+
+    #[cfg(feature = "background-tasks")]
+    fn start_event_listener(&self, token: &str) -> EventListener {
         let (event_emitter, event_listener) = tokio::sync::mpsc::channel(64);
+        let (events, _) = tokio::sync::broadcast::channel(256);
         let event_listener_task = tokio::spawn({
             let client = self.inner.clone();
+            let events = events.clone();
             let token = token.to_string();
 
             async move {
-                let response = client
-                    .clone()
-                    .listen_session_events(pb::StreamRequest { token })
-                    .await
-                    .unwrap();
-
-                let mut stream = response.into_inner();
+                let mut backoff = EVENT_STREAM_MIN_BACKOFF;
 
                 loop {
-                    match stream.message().await {
-                        Ok(Some(event)) => {
-                            for message in event.messages {
-                                use pb::event::message::Value;
-
-                                let Some(value) = message.value else {
-                                    continue;
-                                };
-
-                                match &value {
-                                    Value::AccountShow(_) => {
-                                        event_emitter
-                                            .send(value)
-                                            .await
-                                            .expect("Event receiver dropped");
-                                    }
-                                    message => {
-                                        // TODO: Properly log other messages in debug logs
-                                        dbg!(message);
+                    let response = client
+                        .clone()
+                        .listen_session_events(pb::StreamRequest {
+                            token: token.clone(),
+                        })
+                        .await;
+
+                    let error = match response {
+                        Ok(response) => {
+                            let mut stream = response.into_inner();
+
+                            loop {
+                                match stream.message().await {
+                                    Ok(Some(event)) => {
+                                        backoff = EVENT_STREAM_MIN_BACKOFF;
+
+                                        for message in event.messages {
+                                            use pb::event::message::Value;
+
+                                            let Some(value) = message.value else {
+                                                continue;
+                                            };
+
+                                            match &value {
+                                                Value::AccountShow(_) => {
+                                                    event_emitter
+                                                        .send(value)
+                                                        .await
+                                                        .expect("Event receiver dropped");
+                                                }
+                                                _ => {
+                                                    // Nobody listening for events is fine, they're
+                                                    // dropped
+                                                    let _ = events.send(value);
+                                                }
+                                            }
+                                        }
                                     }
+                                    // anytype-heart closed the stream, most likely because it's
+                                    // shutting down, so there's nothing left to listen to
+                                    Ok(None) => return,
+                                    Err(error) => break error,
                                 }
                             }
                         }
-                        Ok(None) => {
-                            tokio::task::yield_now().await;
-                        }
-                        Err(error) => {
-                            // TODO: Anything we need to do here?
-                            dbg!(error);
-                            tokio::task::yield_now().await;
-                        }
+                        Err(error) => error,
+                    };
+
+                    // A rejected token stays rejected, only other failures are worth retrying
+                    if matches!(
+                        error.code(),
+                        tonic::Code::Unauthenticated | tonic::Code::PermissionDenied
+                    ) {
+                        tracing::warn!(
+                            %error,
+                            "anytype-heart rejected the event stream's token, no longer listening"
+                        );
+                        return;
                     }
+
+                    tracing::warn!(
+                        %error,
+                        ?backoff,
+                        "anytype-heart event stream failed, resubscribing"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(EVENT_STREAM_MAX_BACKOFF);
                 }
             }
         });
 
-        (event_listener, event_listener_task)
+        (
+            event_listener,
+            events,
+            EventListenerTask(event_listener_task),
+        )
     }
 
     pub async fn create_account(
         mut self,
         name: &str,
-    ) -> Result<(String, AuthorizedAnytypeClient), tonic::Status> {
-        let root_path = self
-            .calculate_root_path()?
-            .into_os_string()
-            .into_string()
-            .expect("non utf-8 path root_path");
+    ) -> Result<(String, AuthorizedAnytypeClient), AuthError> {
+        let root_path = self.root_path_string()?;
 
         let response = self
             .inner
@@ -345,24 +768,59 @@ impl AnytypeClient {
             .await?
             .into_inner();
 
-        if let Some(error) = response.error {
+        if let Some(error) = &response.error {
             use pb::rpc::wallet::create::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-                Code::FailedToCreateLocalRepo => {
-                    return Err(tonic::Status::internal(error.description))
-                }
+            if error.code() == Code::FailedToCreateLocalRepo {
+                return Err(AuthError::RepoCreationFailed(error.description.clone()));
             }
         }
+        check_response_error(response.error)?;
 
         let mnemonic = response.mnemonic;
-        let token = self.create_wallet_session(&mnemonic).await?;
+        let client = self
+            .create_account_in_wallet(root_path, &mnemonic, name)
+            .await?;
+
+        Ok((mnemonic, client))
+    }
 
-        let (event_listener, event_listener_task) = self.start_event_listener(&token);
+    /// Create a new account from a known mnemonic rather than a random one, for reproducing a
+    /// known account in tests and debugging
+    ///
+    /// The root path must not hold that account already, use [AnytypeClient::authenticate] to
+    /// log back into it
+    pub async fn create_account_with_mnemonic(
+        mut self,
+        mnemonic: &str,
+        name: &str,
+    ) -> Result<AuthorizedAnytypeClient, AuthError> {
+        let root_path = self.root_path_string()?;
 
-        self.set_metrics().await?;
+        self.recover_wallet(&root_path, mnemonic).await?;
+
+        self.create_account_in_wallet(root_path, mnemonic, name)
+            .await
+    }
+
+    async fn create_account_in_wallet(
+        mut self,
+        root_path: String,
+        mnemonic: &str,
+        name: &str,
+    ) -> Result<AuthorizedAnytypeClient, AuthError> {
+        let token = self.create_wallet_session(mnemonic).await?;
+
+        let (event_listener, events, event_listener_task) =
+            match self.maybe_start_event_listener(&token) {
+                Some((event_listener, events, event_listener_task)) => (
+                    Some(event_listener),
+                    Some(events),
+                    Some(event_listener_task),
+                ),
+                None => (None, None, None),
+            };
+
+        self.set_metrics().await;
 
         let response = self
             .inner
@@ -373,36 +831,31 @@ impl AnytypeClient {
                 disable_local_network_sync: self.disable_local_network_sync,
                 network_mode: self.network_mode,
                 network_custom_config_file_path: String::new(),
-                prefer_yamux_transport: false,
+                prefer_yamux_transport: self.prefer_yamux_transport,
                 avatar: None,
             })
             .await?
             .into_inner();
 
-        if let Some(error) = response.error {
-            use pb::rpc::account::create::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-                _ => todo!("So many unique error types..."),
-            }
-        }
+        check_response_error(response.error)?;
 
-        Ok((
-            mnemonic,
-            AuthorizedAnytypeClient {
-                client: Client {
-                    inner: Arc::new(ClientInner {
-                        grpc: self.inner,
-                        token,
-                    }),
-                },
-                account: Self::account_or_error(response.account)?,
-                event_listener,
-                event_listener_task,
+        Ok(AuthorizedAnytypeClient {
+            client: Client {
+                inner: Arc::new(ClientInner {
+                    grpc: self.inner.clone(),
+                    token,
+                    network_sync: self.network_sync,
+                    check_well_formed_values: self.check_well_formed_values,
+                    max_concurrent_requests: self.max_concurrent_requests,
+                    events,
+                    shutdown_on_drop: AtomicBool::new(self.shutdown_on_drop),
+                }),
             },
-        ))
+            account: Self::account_or_error(response.account)?,
+            event_listener,
+            event_listener_task,
+            unauthenticated: self,
+        })
     }
 
     pub fn with_root_path<P: AsRef<Path>>(self, path: P) -> Self {
@@ -426,12 +879,84 @@ impl AnytypeClient {
         };
 
         Self {
+            network_sync,
             disable_local_network_sync,
             network_mode: network_mode.into(),
             ..self
         }
     }
 
+    /// Prefer the yamux transport over the default one when connecting to other peers, which is
+    /// necessary on some networks where the default transport fails to connect
+    pub fn with_prefer_yamux(self, prefer_yamux_transport: bool) -> Self {
+        Self {
+            prefer_yamux_transport,
+            ..self
+        }
+    }
+
+    /// Don't spawn a background task listening to anytype-heart's events
+    ///
+    /// The one event needed while authenticating is instead awaited inline
+    pub fn without_event_listener(self) -> Self {
+        Self {
+            spawn_event_listener: false,
+            ..self
+        }
+    }
+
+    /// Don't shut anytype-heart down when the client is dropped
+    ///
+    /// Useful when anytype-heart's lifetime is managed elsewhere, or for short-lived programs
+    /// whose runtime would exit before the shutdown completes anyway. anytype-heart can still be
+    /// shut down explicitly with [AuthorizedAnytypeClient::shutdown]
+    pub fn without_shutdown_on_drop(self) -> Self {
+        Self {
+            shutdown_on_drop: false,
+            ..self
+        }
+    }
+
+    /// Reject Url, Email and Phone relation values that clearly don't look like one instead of
+    /// sending them to anytype-heart as is, which doesn't validate them at all
+    pub fn with_well_formed_values(self) -> Self {
+        Self {
+            check_well_formed_values: true,
+            ..self
+        }
+    }
+
+    /// Limit how many requests batch operations, like creating an object type with many
+    /// recommended relations, send to anytype-heart at once. Defaults to 8
+    pub fn with_max_concurrent_requests(self, limit: NonZeroUsize) -> Self {
+        Self {
+            max_concurrent_requests: limit.get(),
+            ..self
+        }
+    }
+
+    async fn wait_account_id_in_stream(
+        stream: &mut tonic::Streaming<pb::Event>,
+    ) -> Result<Option<String>, tonic::Status> {
+        use pb::event::message::Value;
+
+        while let Some(event) = stream.message().await? {
+            for message in event.messages {
+                let Some(Value::AccountShow(show)) = message.value else {
+                    continue;
+                };
+
+                let Some(account) = show.account else {
+                    continue;
+                };
+
+                return Ok(Some(account.id));
+            }
+        }
+
+        Ok(None)
+    }
+
     async fn wait_account_id_event(
         event_listener: &mut tokio::sync::mpsc::Receiver<pb::event::message::Value>,
     ) -> Option<String> {
@@ -457,7 +982,11 @@ impl AnytypeClient {
         None
     }
 
-    async fn set_metrics(&self) -> Result<(), tonic::Status> {
+    /// Report the platform to anytype-heart's telemetry
+    ///
+    /// Metrics aren't needed for anything else to work and not every anytype-heart build supports
+    /// them the same way, so failing to set them only logs a warning
+    async fn set_metrics(&self) {
         let response = self
             .inner
             .clone()
@@ -465,19 +994,12 @@ impl AnytypeClient {
                 platform: "Mac".to_string(),
                 version: "0.39.0".to_string(),
             })
-            .await?
-            .into_inner();
+            .await
+            .and_then(|response| check_response_error(response.into_inner().error));
 
-        if let Some(error) = response.error {
-            use pb::rpc::metrics::set_parameters::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
+        if let Err(error) = response {
+            tracing::warn!(%error, "failed to set anytype-heart metrics parameters, continuing");
         }
-
-        Ok(())
     }
 
     fn account_or_error(account: Option<Account>) -> Result<Account, tonic::Status> {
@@ -496,6 +1018,130 @@ impl AuthorizedAnytypeClient {
         &self.account
     }
 
+    /// The session token anytype-heart issued for this client, which can be stored and passed to
+    /// [AnytypeClient::authenticate_with_token] to reconnect without the mnemonic
+    pub fn token(&self) -> &str {
+        &self.client.token
+    }
+
+    /// Check that anytype-heart is responsive and that this client's session is still valid
+    ///
+    /// Fails with [TokenExpired] if the session is no longer valid
+    pub async fn ping(&self) -> Result<(), tonic::Status> {
+        let response = self
+            .client
+            .request(
+                pb::rpc::object::search::Request {
+                    limit: 1,
+                    keys: vec!["id".to_string()],
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_search(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    /// Log out of this account while keeping the connection to anytype-heart open, handing back
+    /// a client that can authenticate with a different account
+    ///
+    /// Spaces opened through this client stop working once it's logged out, and so does its
+    /// session token, any client authenticated with it fails with [TokenExpired] from then on
+    pub async fn logout(self) -> Result<AnytypeClient, tonic::Status> {
+        let response = self
+            .client
+            .request(
+                pb::rpc::account::stop::Request { remove_data: false },
+                |mut grpc, request| async move { grpc.account_stop(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let response = self
+            .client
+            .request(
+                pb::rpc::wallet::close_session::Request {
+                    token: self.client.token.clone(),
+                },
+                |mut grpc, request| async move { grpc.wallet_close_session(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        self.client.shutdown_on_drop.store(false, Ordering::Relaxed);
+
+        Ok(self.unauthenticated.clone())
+    }
+
+    /// Shut anytype-heart down and wait for it to acknowledge, instead of leaving it to a
+    /// background task when the client is dropped
+    pub async fn shutdown(self) -> Result<(), tonic::Status> {
+        self.client.shutdown_on_drop.store(false, Ordering::Relaxed);
+
+        ClientInner::app_shutdown(self.client.grpc.clone(), &self.client.token).await
+    }
+
+    /// The object holding this account's profile, such as its name and icon
+    pub async fn profile(&self) -> Result<Object, tonic::Status> {
+        let (space, id) = self.profile_space_and_id().await?;
+
+        let Some(profile) = space
+            .get_object_with_layouts(id, &[ObjectLayout::Profile])
+            .await?
+        else {
+            return Err(tonic::Status::not_found(format!(
+                "Profile object {id} of account {} was not found",
+                self.account.id
+            )));
+        };
+
+        Ok(profile)
+    }
+
+    pub async fn set_profile_name(&self, name: &str) -> Result<(), tonic::Status> {
+        let (space, id) = self.profile_space_and_id().await?;
+
+        space
+            .set_detail(id, "name".to_string(), name.to_string().into_prost())
+            .await
+    }
+
+    /// Set an emoji as the icon of this account's profile
+    pub async fn set_profile_icon(&self, icon: &str) -> Result<(), tonic::Status> {
+        let (space, id) = self.profile_space_and_id().await?;
+
+        space
+            .set_detail(id, "iconEmoji".to_string(), icon.to_string().into_prost())
+            .await
+    }
+
+    async fn profile_space_and_id(&self) -> Result<(Space, ObjectId), tonic::Status> {
+        let Some(info) = self.account.info.as_ref() else {
+            return Err(tonic::Status::failed_precondition(
+                "anytype-heart did not respond with the account's info",
+            ));
+        };
+        let id = ObjectId::try_from_prost(prost_types::value::Kind::StringValue(
+            info.profile_object_id.clone(),
+        ))
+        .map_err(|error| tonic::Status::internal(format!("{error}")))?;
+
+        let Some(space) = self.default_space().await? else {
+            return Err(tonic::Status::not_found(format!(
+                "Space of account {} was not found",
+                self.account.id
+            )));
+        };
+
+        Ok((space, id))
+    }
+
     pub async fn default_space(&self) -> Result<Option<Space>, tonic::Status> {
         let Some(info) = self.account.info.as_ref() else {
             return Ok(None);
@@ -505,53 +1151,115 @@ impl AuthorizedAnytypeClient {
     }
 
     pub async fn open_space(&self, space_id: &str) -> Result<Option<Space>, tonic::Status> {
-        let response = self
-            .client
-            .grpc
-            .clone()
-            .workspace_open(RequestWithToken {
-                request: pb::rpc::workspace::open::Request {
-                    space_id: space_id.to_string(),
-                },
-                token: &self.client.token,
-            })
-            .await?
-            .into_inner();
+        self.open_space_with(space_id, false).await
+    }
 
-        if let Some(error) = response.error {
-            use pb::rpc::workspace::open::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                // TODO: This hack will hopefully not last forever, currently anytype-heart doesn't
-                // really give any better way of detecting an incorrect space_id error though
-                Code::UnknownError
-                    if error.description
-                        == "failed to get derived ids: failed to get space: space not exists" =>
-                {
-                    return Ok(None)
-                }
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
-        };
+    /// Open the space with the given id for reading only
+    ///
+    /// Searching and reading objects works as usual, but creating or changing anything in the
+    /// space, including through objects and types read from it, fails with [ReadOnly]. Meant for
+    /// reporting and export tools that must never write by accident
+    ///
+    /// [ReadOnly]: crate::ReadOnly
+    pub async fn open_space_readonly(
+        &self,
+        space_id: &str,
+    ) -> Result<Option<Space>, tonic::Status> {
+        self.open_space_with(space_id, true).await
+    }
 
-        let Some(info) = response.info else {
-            return Err(tonic::Status::internal(
-                "anytype-heart did not respond with a space's info",
-            ));
+    async fn open_space_with(
+        &self,
+        space_id: &str,
+        read_only: bool,
+    ) -> Result<Option<Space>, tonic::Status> {
+        let Some(info) = self.client.workspace_open(space_id).await? else {
+            return Ok(None);
         };
 
+        let mut inner = SpaceInner::new(self.client.clone(), info, self.account.id.clone());
+        inner.read_only = read_only;
+
         Ok(Some(Space {
-            inner: Arc::new(SpaceInner {
-                client: self.client.clone(),
-                info,
-            }),
+            inner: Arc::new(inner),
         }))
     }
-}
 
-impl Drop for AuthorizedAnytypeClient {
-    fn drop(&mut self) {
-        self.event_listener_task.abort();
+    /// Open the space with the given name
+    ///
+    /// Space names aren't guaranteed to be unique, so more than one space with the name is an
+    /// error rather than an arbitrary pick between them
+    pub async fn open_space_by_name(&self, name: &str) -> Result<Option<Space>, tonic::Status> {
+        let mut space_ids = self
+            .space_views()
+            .await?
+            .into_iter()
+            .filter(|(_, space_name)| space_name == name)
+            .map(|(space_id, _)| space_id)
+            .collect::<Vec<_>>();
+
+        match space_ids.len() {
+            0 => Ok(None),
+            1 => self.open_space(&space_ids.swap_remove(0)).await,
+            _ => Err(tonic::Status::failed_precondition(format!(
+                "More than one space with same name {name}"
+            ))),
+        }
+    }
+
+    /// The id and name of every space of this account, read from the space views anytype-heart
+    /// keeps in the account's tech space
+    async fn space_views(&self) -> Result<Vec<(String, String)>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+        use pb::models::block::content::dataview::Filter;
+        use pb::models::object_type::Layout;
+
+        let Some(info) = self.account.info.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let response = self
+            .client
+            .request(
+                pb::rpc::object::search::Request {
+                    filters: vec![
+                        Filter {
+                            operator: Operator::And.into(),
+                            relation_key: "spaceId".to_string(),
+                            condition: Condition::Equal.into(),
+                            value: Some(info.tech_space_id.clone().into_prost()),
+
+                            ..Default::default()
+                        },
+                        Filter {
+                            operator: Operator::And.into(),
+                            relation_key: "layout".to_string(),
+                            condition: Condition::Equal.into(),
+                            value: Some((i32::from(Layout::SpaceView) as f64).into_prost()),
+
+                            ..Default::default()
+                        },
+                    ],
+                    keys: vec!["targetSpaceId".to_string(), "name".to_string()],
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_search(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        response
+            .records
+            .into_iter()
+            .map(|record| {
+                let mut fields = ProstStruct::from(record);
+                let space_id = fields.take::<String>("targetSpaceId")?;
+                let name = fields.take_optional::<String>("name")?.unwrap_or_default();
+
+                Ok((space_id, name))
+            })
+            .collect::<Result<Vec<_>, ProstConversionError>>()
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
     }
 }
@@ -0,0 +1,15 @@
+/// Whether an `obtain_*` call created the value it returned or found it already in the space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Existence {
+    /// The value didn't exist and was created
+    Created,
+    /// The value already existed and was fetched
+    Found,
+}
+
+impl Existence {
+    /// Whether the value was freshly created
+    pub fn is_created(&self) -> bool {
+        matches!(self, Existence::Created)
+    }
+}
@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::pb;
+
+/// Formats anytype-heart can export objects in
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// AnyType's own protobuf based format, this is the format AnyType can re-import
+    Protobuf,
+    Markdown,
+}
+
+impl From<ExportFormat> for pb::models::export::Format {
+    fn from(value: ExportFormat) -> Self {
+        match value {
+            ExportFormat::Protobuf => pb::models::export::Format::Protobuf,
+            ExportFormat::Markdown => pb::models::export::Format::Markdown,
+        }
+    }
+}
+
+/// anytype-heart only supports exporting to a directory on disk, so we export into a unique
+/// temporary directory and read the resulting archive back
+pub(crate) fn temporary_export_dir() -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+
+    std::env::temp_dir().join(format!(
+        "anytype-friend-export-{}-{nanos}",
+        std::process::id()
+    ))
+}
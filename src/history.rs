@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::object::{Object, ObjectId, ObjectLayout};
+use crate::object_type::ObjectType;
+use crate::pb;
+use crate::raw_value::RawValue;
+use crate::relation::{Relation, RelationValue};
+
+/// A past version of an object as recorded by anytype-heart
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectVersion {
+    id: String,
+    author_id: String,
+    author_name: String,
+    time: DateTime<Utc>,
+}
+
+impl ObjectVersion {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The participant id of whoever made the changes in this version
+    pub fn author_id(&self) -> &str {
+        &self.author_id
+    }
+
+    pub fn author_name(&self) -> &str {
+        &self.author_name
+    }
+
+    /// When this version was made
+    pub fn time(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+impl TryFrom<pb::rpc::history::Version> for ObjectVersion {
+    type Error = tonic::Status;
+
+    fn try_from(value: pb::rpc::history::Version) -> Result<Self, Self::Error> {
+        let Some(time) = DateTime::from_timestamp(value.time, 0) else {
+            return Err(tonic::Status::internal(format!(
+                "anytype-heart responded with version {} made at an invalid time {}",
+                value.id, value.time
+            )));
+        };
+
+        Ok(ObjectVersion {
+            id: value.id,
+            author_id: value.author_id,
+            author_name: value.author_name,
+            time,
+        })
+    }
+}
+
+/// An object as it was at a past version, see [Object::at_version]
+///
+/// It can only be read from, changes are made through the [Object] itself
+#[derive(Debug, Clone)]
+pub struct ObjectAtVersion {
+    object: Object,
+    version: ObjectVersion,
+}
+
+impl ObjectAtVersion {
+    pub(crate) fn new(object: Object, version: ObjectVersion) -> Self {
+        Self { object, version }
+    }
+
+    /// The version the object is at
+    pub fn version(&self) -> &ObjectVersion {
+        &self.version
+    }
+
+    pub fn id(&self) -> ObjectId {
+        self.object.id()
+    }
+
+    pub fn name(&self) -> String {
+        self.object.name()
+    }
+
+    pub fn layout(&self) -> ObjectLayout {
+        self.object.layout()
+    }
+
+    /// The type the object had at this version, as that type is now
+    pub async fn ty(&self) -> Result<ObjectType, tonic::Status> {
+        self.object.ty().await
+    }
+
+    /// See [Object::relation_keys]
+    pub fn relation_keys(&self) -> Vec<String> {
+        self.object.relation_keys()
+    }
+
+    /// See [Object::get_raw]
+    pub fn get_raw(&self, key: &str) -> Option<RawValue> {
+        self.object.get_raw(key)
+    }
+
+    /// See [Object::raw_details]
+    pub fn raw_details(&self) -> BTreeMap<String, RawValue> {
+        self.object.raw_details()
+    }
+
+    /// Get the value a relation had at this version, objects referenced by Object relations are
+    /// read as they are now
+    pub async fn get(&self, key: &Relation) -> Option<RelationValue> {
+        self.object.get(key).await
+    }
+}
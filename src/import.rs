@@ -0,0 +1,76 @@
+use crate::object::ObjectId;
+use crate::pb;
+
+/// Formats anytype-heart can import objects from
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// A zip archive or directory in AnyType's own protobuf based format, as produced by
+    /// [ExportFormat::Protobuf](crate::ExportFormat::Protobuf)
+    Protobuf,
+    /// A directory of markdown files
+    Markdown,
+}
+
+impl ImportFormat {
+    pub(crate) fn into_request_parts(
+        self,
+        path: String,
+    ) -> (
+        pb::models::import::Type,
+        pb::rpc::object::import::request::Params,
+    ) {
+        use pb::rpc::object::import::request::{MarkdownParams, Params, PbParams};
+
+        match self {
+            ImportFormat::Protobuf => (
+                pb::models::import::Type::Pb,
+                Params::PbParams(PbParams {
+                    path: vec![path],
+
+                    ..Default::default()
+                }),
+            ),
+            ImportFormat::Markdown => (
+                pb::models::import::Type::Markdown,
+                Params::MarkdownParams(MarkdownParams { path: vec![path] }),
+            ),
+        }
+    }
+}
+
+/// How an import treats objects that fail to import
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Any failure aborts the whole import
+    #[default]
+    AllOrNothing,
+    /// Objects that fail to import are skipped and the rest are imported, the failures are
+    /// reported in [ImportReport::skipped]
+    IgnoreErrors,
+}
+
+impl From<ImportMode> for pb::rpc::object::import::request::Mode {
+    fn from(value: ImportMode) -> Self {
+        match value {
+            ImportMode::AllOrNothing => pb::rpc::object::import::request::Mode::AllOrNothing,
+            ImportMode::IgnoreErrors => pb::rpc::object::import::request::Mode::IgnoreErrors,
+        }
+    }
+}
+
+/// Summary of a finished import
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    /// The collection anytype-heart groups imported objects into, if it created one
+    pub collection: Option<ObjectId>,
+    /// The objects the import created, as linked from [ImportReport::collection]. Empty when no
+    /// collection was created
+    pub objects: Vec<ObjectId>,
+    /// How many objects were imported
+    pub objects_count: u64,
+    /// The failures skipped over with [ImportMode::IgnoreErrors]
+    ///
+    /// anytype-heart doesn't break these down per object, they're reported together as the one
+    /// error the import would have failed with otherwise
+    pub skipped: Option<tonic::Status>,
+}
@@ -1,10 +1,22 @@
+mod bookmark;
 mod client;
+mod existence;
+mod export;
+mod history;
+mod import;
+mod member;
 mod object;
 mod object_type;
+mod plan;
 mod prost_ext;
+mod raw_value;
 mod relation;
+mod relation_option;
 mod request;
+mod response;
 mod space;
+mod space_info;
+mod subscription;
 mod unique_key;
 mod pb {
     pub(crate) mod models {
@@ -14,8 +26,29 @@ mod pb {
     tonic::include_proto!("anytype");
 }
 
-pub use client::{AnytypeClient, AuthorizedAnytypeClient, NetworkSync};
-pub use object::{ObjectDescription, ObjectSpec};
-pub use object_type::ObjectTypeSpec;
-pub use relation::{Relation, RelationFormat, RelationSpec, RelationValue};
-pub use space::Space;
+pub use bookmark::{Bookmark, CreatedBookmark};
+pub use client::{
+    AnytypeClient, AuthError, AuthorizedAnytypeClient, NetworkSync, RootPathNotUtf8,
+    RootPathNotWritable, TokenExpired,
+};
+pub use existence::Existence;
+pub use export::ExportFormat;
+pub use history::{ObjectAtVersion, ObjectVersion};
+pub use import::{ImportFormat, ImportMode, ImportReport};
+pub use member::{InviteLink, Permission, SharingRequiresNetworkSync, SpaceMember};
+pub use object::{
+    Object, ObjectDescription, ObjectId, ObjectLayout, ObjectSpec, ParseObjectIdError,
+};
+pub use object_type::{
+    BundledType, ObjectType, ObjectTypeId, ObjectTypeSpec, ObjectTypeSpecBuilder, RelationGroup,
+};
+pub use plan::CreationPlan;
+pub use raw_value::RawValue;
+pub use relation::{
+    CoerceError, FormatChangeUnsupported, InvalidRelationValue, ParseRelationFormatError, Relation,
+    RelationDescription, RelationFormat, RelationId, RelationSpec, RelationValue,
+};
+pub use relation_option::{RelationOption, RelationOptionId};
+pub use space::{EmptyName, ReadOnly, RecommendedRelationsMismatch, Space};
+pub use space_info::SpaceInfo;
+pub use subscription::{ObjectEvent, SubscriptionStream};
@@ -0,0 +1,157 @@
+use std::fmt::Display;
+
+use crate::{
+    pb::models::{ParticipantPermissions, ParticipantStatus},
+    prost_ext::{ProstConversionError, ProstStruct, TryFromProst},
+};
+
+/// What a member of a space is allowed to do in it
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Permission {
+    Reader,
+    Writer,
+    Owner,
+    NoPermissions,
+}
+
+impl From<ParticipantPermissions> for Permission {
+    fn from(value: ParticipantPermissions) -> Self {
+        match value {
+            ParticipantPermissions::Reader => Permission::Reader,
+            ParticipantPermissions::Writer => Permission::Writer,
+            ParticipantPermissions::Owner => Permission::Owner,
+            ParticipantPermissions::NoPermissions => Permission::NoPermissions,
+        }
+    }
+}
+
+/// Someone a space is shared with, including its owner
+///
+/// Members are the participant objects of a space, which unlike other objects aren't identified
+/// by a CID and so are never resolved as an [Object](crate::Object)
+#[derive(Debug, Clone)]
+pub struct SpaceMember {
+    id: String,
+    identity: String,
+    name: String,
+    icon_image: Option<String>,
+    permission: Permission,
+    pub(crate) status: ParticipantStatus,
+}
+
+impl SpaceMember {
+    /// The id of the member's participant object, derived from the space and their identity
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The identity of the member's account, this is the same across every space they're in
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The id of the file object holding the member's profile picture
+    pub fn icon_image(&self) -> Option<&str> {
+        self.icon_image.as_deref()
+    }
+
+    pub fn permission(&self) -> Permission {
+        self.permission
+    }
+}
+
+impl TryFromProst for SpaceMember {
+    type Input = prost_types::Struct;
+
+    fn try_from_prost(input: Self::Input) -> Result<Self, ProstConversionError>
+    where
+        Self: Sized,
+    {
+        use crate::pb::models::object_type::Layout;
+
+        let mut value = ProstStruct::from(input);
+
+        let layout = value.take_enum::<Layout>("layout")?;
+        assert!(layout == Layout::Participant);
+
+        // Participant ids are derived from the space and identity instead of being CIDs
+        let id = value.take::<String>("id")?;
+        let identity = value.take::<String>("identity")?;
+        let name = value.take_optional::<String>("name")?.unwrap_or_default();
+        // AnyType stores a missing icon as an empty string
+        let icon_image = value
+            .take_optional::<String>("iconImage")?
+            .filter(|icon| !icon.is_empty());
+        let permission = value.take_enum::<ParticipantPermissions>("participantPermissions")?;
+        let status = value.take_enum::<ParticipantStatus>("participantStatus")?;
+
+        Ok(Self {
+            id,
+            identity,
+            name,
+            icon_image,
+            permission: Permission::from(permission),
+            status,
+        })
+    }
+}
+
+impl crate::space::SearchOutput for SpaceMember {
+    const LAYOUT: &'static [crate::pb::models::object_type::Layout] =
+        &[crate::pb::models::object_type::Layout::Participant];
+    type Id = String;
+
+    fn id(&self) -> Self::Id {
+        self.id.clone()
+    }
+}
+
+/// A link others can use to request to join a shared space
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteLink {
+    cid: String,
+    key: String,
+}
+
+impl InviteLink {
+    pub(crate) fn new(cid: String, key: String) -> Self {
+        Self { cid, key }
+    }
+
+    /// The CID of the invite, which identifies it
+    pub fn cid(&self) -> &str {
+        &self.cid
+    }
+
+    /// The key of the invite, which is needed to read it
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The shareable link AnyType apps accept
+    pub fn url(&self) -> String {
+        format!("https://invite.any.coop/{}#{}", self.cid, self.key)
+    }
+}
+
+/// Error returned when trying to share a space of a client that isn't syncing over the network
+#[derive(Debug)]
+pub struct SharingRequiresNetworkSync;
+
+impl Display for SharingRequiresNetworkSync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Sharing a space requires the client to use NetworkSync::Sync")
+    }
+}
+
+impl std::error::Error for SharingRequiresNetworkSync {}
+
+impl From<SharingRequiresNetworkSync> for tonic::Status {
+    fn from(value: SharingRequiresNetworkSync) -> Self {
+        tonic::Status::failed_precondition(format!("{value}"))
+    }
+}
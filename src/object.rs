@@ -1,16 +1,24 @@
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, VecDeque},
     fmt::{Debug, Display},
+    str::FromStr,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
 };
 
 use chrono::DateTime;
 use cid::CidGeneric;
 
 use crate::{
+    bookmark::Bookmark,
+    export::ExportFormat,
+    history::{ObjectAtVersion, ObjectVersion},
     object_type::{ObjectType, ObjectTypeId, ObjectTypeUnresolved},
+    pb::{event::message::Value, models::object_type::Layout},
     prost_ext::{IntoProstValue, ProstConversionError, ProstStruct, TryFromProst},
-    relation::{IncompatibleRelationValue, Relation, RelationFormat, RelationValue},
+    raw_value::RawValue,
+    relation::{InvalidRelationValue, Relation, RelationFormat, RelationValue},
     space::Space,
+    subscription::SubscriptionStream,
 };
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -41,9 +49,33 @@ impl TryFromProst for ObjectId {
 
     fn try_from_prost(kind: Self::Input) -> Result<Self, ProstConversionError> {
         let string = String::try_from_prost(kind)?;
-        let cid = CidGeneric::<32>::try_from(string)
-            .expect("ObjectIds are always valid CID with 32 bytes");
-        Ok(ObjectId(cid))
+        string
+            .parse()
+            .map_err(|_| ProstConversionError::InvalidCid(string))
+    }
+}
+
+/// Error returned when parsing an id from a string that isn't a valid CID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseObjectIdError(pub String);
+
+impl Display for ParseObjectIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a valid object id", self.0)
+    }
+}
+
+impl std::error::Error for ParseObjectIdError {}
+
+/// Parses the ids [ObjectId]'s [Display] implementation produces, such as ones stored outside of
+/// anytype
+impl FromStr for ObjectId {
+    type Err = ParseObjectIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CidGeneric::<32>::try_from(s)
+            .map(ObjectId)
+            .map_err(|_| ParseObjectIdError(s.to_string()))
     }
 }
 
@@ -56,27 +88,64 @@ pub struct ObjectDescription {
     pub ty: ObjectType,
     pub name: String,
     pub relations: HashMap<Relation, RelationValue>,
+    /// Takes precedence over a value for the Description relation in `relations`
+    pub description: Option<String>,
+    /// An emoji to use as the object's icon
+    pub icon: Option<String>,
+    /// Any other details to set on the object that aren't modeled by anytype-friend, keyed by
+    /// their raw detail key. These are sent as is without any validation
+    pub details: HashMap<String, RawValue>,
+    /// Pre-fill the object from its type's default template, like the AnyType apps do, see
+    /// [ObjectType::default_template]. Values given here take precedence over the template's
+    pub use_default_template: bool,
 }
 
 impl ObjectSpec {
+    pub fn new(ty: ObjectType, name: impl Into<String>) -> Self {
+        ObjectSpec {
+            ty,
+            name: name.into(),
+        }
+    }
+
     pub fn as_description(&self) -> ObjectDescription {
         ObjectDescription {
             ty: self.ty.clone(),
             name: self.name.clone(),
             relations: HashMap::new(),
+            description: None,
+            icon: None,
+            details: HashMap::new(),
+            use_default_template: false,
         }
     }
 }
 
 impl TryFrom<ObjectDescription> for prost_types::Struct {
-    type Error = IncompatibleRelationValue;
+    type Error = InvalidRelationValue;
 
     fn try_from(value: ObjectDescription) -> Result<Self, Self::Error> {
+        value.into_struct(false)
+    }
+}
+
+impl ObjectDescription {
+    /// Validate this description's relation values and convert it into the details anytype-heart
+    /// expects, also checking that Url, Email and Phone values look like one if
+    /// `check_well_formed` is set
+    pub(crate) fn into_struct(
+        self,
+        check_well_formed: bool,
+    ) -> Result<prost_types::Struct, InvalidRelationValue> {
         let mut fields = BTreeMap::new();
-        fields.insert("name".to_string(), value.name.into_prost());
         fields.extend(
-            value
-                .relations
+            self.details
+                .into_iter()
+                .map(|(key, value)| (key, value.into_prost())),
+        );
+        fields.insert("name".to_string(), self.name.into_prost());
+        fields.extend(
+            self.relations
                 .into_iter()
                 // This is necessary because anytype-heart does not validate ANY of the relation
                 // values sent to it. Yes if you set a relation format to Number and then send a
@@ -84,20 +153,126 @@ impl TryFrom<ObjectDescription> for prost_types::Struct {
                 // if you query for it later without any errors
                 .map(|(relation, value)| {
                     relation
-                        .validate(value)
+                        .validate(value, check_well_formed)
                         .map(|detail| detail.into_raw_parts())
                 })
-                .collect::<Result<Vec<_>, Self::Error>>()?,
+                .collect::<Result<Vec<_>, InvalidRelationValue>>()?,
         );
 
+        if let Some(description) = self.description {
+            fields.insert("description".to_string(), description.into_prost());
+        }
+
+        if let Some(icon) = self.icon {
+            fields.insert("iconEmoji".to_string(), icon.into_prost());
+        }
+
         Ok(prost_types::Struct { fields })
     }
 }
 
+/// The layout of an object, which decides how AnyType apps render it and which of its fields are
+/// meaningful
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ObjectLayout {
+    Basic,
+    Profile,
+    Todo,
+    Set,
+    ObjectType,
+    Relation,
+    File,
+    Dashboard,
+    Image,
+    Note,
+    Space,
+    Bookmark,
+    RelationOptionsList,
+    RelationOption,
+    Collection,
+    Audio,
+    Video,
+    Date,
+    SpaceView,
+    Participant,
+    Pdf,
+    /// A layout anytype-friend doesn't know about yet
+    Other(i32),
+}
+
+impl From<Layout> for ObjectLayout {
+    fn from(value: Layout) -> Self {
+        match value {
+            Layout::Basic => ObjectLayout::Basic,
+            Layout::Profile => ObjectLayout::Profile,
+            Layout::Todo => ObjectLayout::Todo,
+            Layout::Set => ObjectLayout::Set,
+            Layout::ObjectType => ObjectLayout::ObjectType,
+            Layout::Relation => ObjectLayout::Relation,
+            Layout::File => ObjectLayout::File,
+            Layout::Dashboard => ObjectLayout::Dashboard,
+            Layout::Image => ObjectLayout::Image,
+            Layout::Note => ObjectLayout::Note,
+            Layout::Space => ObjectLayout::Space,
+            Layout::Bookmark => ObjectLayout::Bookmark,
+            Layout::RelationOptionsList => ObjectLayout::RelationOptionsList,
+            Layout::RelationOption => ObjectLayout::RelationOption,
+            Layout::Collection => ObjectLayout::Collection,
+            Layout::Audio => ObjectLayout::Audio,
+            Layout::Video => ObjectLayout::Video,
+            Layout::Date => ObjectLayout::Date,
+            Layout::SpaceView => ObjectLayout::SpaceView,
+            Layout::Participant => ObjectLayout::Participant,
+            Layout::Pdf => ObjectLayout::Pdf,
+        }
+    }
+}
+
+impl ObjectLayout {
+    /// The layout for anytype-heart's representation of it, [ObjectLayout::Other] for ones newer
+    /// than anytype-friend
+    pub(crate) fn from_raw(layout: i32) -> Self {
+        Layout::try_from(layout)
+            .map(ObjectLayout::from)
+            .unwrap_or(ObjectLayout::Other(layout))
+    }
+
+    /// The layout as anytype-heart represents it
+    pub(crate) fn into_raw(self) -> i32 {
+        let layout = match self {
+            ObjectLayout::Basic => Layout::Basic,
+            ObjectLayout::Profile => Layout::Profile,
+            ObjectLayout::Todo => Layout::Todo,
+            ObjectLayout::Set => Layout::Set,
+            ObjectLayout::ObjectType => Layout::ObjectType,
+            ObjectLayout::Relation => Layout::Relation,
+            ObjectLayout::File => Layout::File,
+            ObjectLayout::Dashboard => Layout::Dashboard,
+            ObjectLayout::Image => Layout::Image,
+            ObjectLayout::Note => Layout::Note,
+            ObjectLayout::Space => Layout::Space,
+            ObjectLayout::Bookmark => Layout::Bookmark,
+            ObjectLayout::RelationOptionsList => Layout::RelationOptionsList,
+            ObjectLayout::RelationOption => Layout::RelationOption,
+            ObjectLayout::Collection => Layout::Collection,
+            ObjectLayout::Audio => Layout::Audio,
+            ObjectLayout::Video => Layout::Video,
+            ObjectLayout::Date => Layout::Date,
+            ObjectLayout::SpaceView => Layout::SpaceView,
+            ObjectLayout::Participant => Layout::Participant,
+            ObjectLayout::Pdf => Layout::Pdf,
+            ObjectLayout::Other(layout) => return layout,
+        };
+
+        layout.into()
+    }
+}
+
 pub(crate) struct ObjectUnresolved {
     id: ObjectId,
     name: String,
     pub(crate) ty: ObjectTypeId,
+    layout: ObjectLayout,
     relations: prost_types::Struct,
 }
 
@@ -107,11 +282,37 @@ impl ObjectUnresolved {
             space,
 
             id: self.id,
-            name: self.name,
-            ty: self.ty,
-            relations: self.relations,
+            layout: self.layout,
+            state: Arc::new(RwLock::new(ObjectState {
+                name: self.name,
+                ty: self.ty,
+                relations: self.relations,
+            })),
+            writes: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
+
+    /// Same as [TryFromProst::try_from_prost] but accepts objects of layouts outside of
+    /// [SearchOutput::LAYOUT](crate::space::SearchOutput::LAYOUT), for searches that explicitly
+    /// ask for other layouts
+    pub(crate) fn try_from_any_layout(
+        input: prost_types::Struct,
+    ) -> Result<Self, ProstConversionError> {
+        let mut value = ProstStruct::from(input);
+
+        let layout = ObjectLayout::from_raw(value.take::<f64>("layout")? as i32);
+        let id = value.take::<ObjectId>("id")?;
+        let name = value.take::<String>("name")?;
+        let ty = value.take::<ObjectTypeId>("type")?;
+
+        Ok(Self {
+            id,
+            name,
+            ty,
+            layout,
+            relations: value.into_inner(),
+        })
+    }
 }
 
 impl crate::space::SearchOutput for ObjectUnresolved {
@@ -120,6 +321,10 @@ impl crate::space::SearchOutput for ObjectUnresolved {
         crate::pb::models::object_type::Layout::Bookmark,
     ];
     type Id = ObjectId;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
 }
 
 impl TryFromProst for ObjectUnresolved {
@@ -129,108 +334,221 @@ impl TryFromProst for ObjectUnresolved {
     where
         Self: Sized,
     {
-        use crate::pb::models::object_type::Layout;
-
-        let mut value = ProstStruct::from(input);
+        let object = Self::try_from_any_layout(input)?;
+        assert!(<Self as crate::space::SearchOutput>::LAYOUT
+            .iter()
+            .any(|layout| ObjectLayout::from(*layout) == object.layout));
 
-        let layout = value.take_enum::<Layout>("layout")?;
-        assert!(&[Layout::Basic, Layout::Bookmark].contains(&layout));
-
-        let id = value.take::<ObjectId>("id")?;
-        let name = value.take::<String>("name")?;
-        let ty = value.take::<ObjectTypeId>("type")?;
-
-        Ok(Self {
-            id,
-            name,
-            ty,
-            relations: value.into_inner(),
-        })
+        Ok(object)
     }
 }
 
+/// The parts of an object that can change after it was fetched
+#[derive(Debug)]
+struct ObjectState {
+    name: String,
+    ty: ObjectTypeId,
+    relations: prost_types::Struct,
+}
+
+/// An object in a space
+///
+/// Clones of an object share its state, so changes made through any of them are visible to all.
+/// Changes to relation values made through clones of the same object are applied one at a time,
+/// see [Object::set]
 #[derive(Debug, Clone)]
 pub struct Object {
     space: Space,
 
     id: ObjectId,
-    name: String,
-    pub(crate) ty: ObjectTypeId,
-    relations: prost_types::Struct,
+    layout: ObjectLayout,
+    state: Arc<RwLock<ObjectState>>,
+    /// Held while a relation value is being changed, so that reading the previous value and
+    /// writing the new one happen together
+    writes: Arc<tokio::sync::Mutex<()>>,
 }
 
 impl Object {
+    fn state(&self) -> RwLockReadGuard<'_, ObjectState> {
+        self.state.read().expect("object state lock was poisoned")
+    }
+
+    fn state_mut(&self) -> RwLockWriteGuard<'_, ObjectState> {
+        self.state.write().expect("object state lock was poisoned")
+    }
+
     pub fn id(&self) -> ObjectId {
         self.id
     }
 
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> String {
+        self.state().name.clone()
+    }
+
+    pub(crate) fn type_id(&self) -> ObjectTypeId {
+        self.state().ty
+    }
+
+    pub fn layout(&self) -> ObjectLayout {
+        self.layout
+    }
+
+    /// This object as a [Bookmark], `None` unless it has the bookmark layout
+    pub fn as_bookmark(&self) -> Option<Bookmark> {
+        (self.layout == ObjectLayout::Bookmark).then(|| Bookmark::new(self.clone()))
     }
 
     // TODO: I don't think it's ideal this is async, we might want to resolve objects in a way that
     // allows us to pass their type with space
     pub async fn ty(&self) -> Result<ObjectType, tonic::Status> {
         self.space
-            .get_objects::<ObjectTypeUnresolved>([self.ty])
+            .get_objects::<ObjectTypeUnresolved>([self.type_id()])
             .await?
             .swap_remove(0)
             .slow_resolve(self.space.clone())
             .await
     }
+
+    /// Get the name of this object's type without resolving its recommended relations
+    pub async fn type_name(&self) -> Result<String, tonic::Status> {
+        let ty = self.type_id();
+        let Some(object_type) = self
+            .space
+            .get_objects::<ObjectTypeUnresolved>([ty])
+            .await?
+            .pop()
+        else {
+            return Err(tonic::Status::not_found(format!(
+                "Object type {} of object `{}` was not found",
+                ty,
+                self.name()
+            )));
+        };
+
+        Ok(object_type.name)
+    }
 }
 
+/// Relation keys anytype-heart keeps on every object for its own bookkeeping
+const INTERNAL_RELATION_KEYS: &[&str] = &[
+    "spaceId",
+    "isHidden",
+    "isArchived",
+    "isDeleted",
+    "isFavorite",
+    "internalFlags",
+    "restrictions",
+    "snippet",
+    "featuredRelations",
+    "workspaceId",
+    "backlinks",
+    "links",
+    "lastOpenedDate",
+    "lastModifiedBy",
+    "lastModifiedDate",
+    "createdDate",
+    "creator",
+    "syncStatus",
+    "syncDate",
+    "syncError",
+];
+
 impl Object {
+    /// The keys of relations set on this object, without resolving them into [Relation]s
+    ///
+    /// Keys anytype-heart uses for bookkeeping and cleared relations are left out
+    pub fn relation_keys(&self) -> Vec<String> {
+        let mut keys = self
+            .state()
+            .relations
+            .fields
+            .iter()
+            .filter(|(key, _)| !INTERNAL_RELATION_KEYS.contains(&key.as_str()))
+            .filter(|(_, value)| {
+                !matches!(
+                    value.kind,
+                    None | Some(prost_types::value::Kind::NullValue(_))
+                )
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+
+        keys
+    }
+
+    /// Get the value stored at a raw detail key as is, without knowing the format of its relation
+    ///
+    /// Cleared relations are returned as [RawValue::Null] rather than [None], which is reserved
+    /// for keys that aren't set on this object at all
+    pub fn get_raw(&self, key: &str) -> Option<RawValue> {
+        self.state()
+            .relations
+            .fields
+            .get(key)?
+            .kind
+            .clone()
+            .map(RawValue::from)
+    }
+
+    /// Get the elements of a list detail as is, even when they aren't all of the same kind
+    ///
+    /// Details holding a single value are read as a list of that value, and cleared ones as an
+    /// empty list
+    pub fn get_raw_list(&self, key: &str) -> Option<Vec<RawValue>> {
+        self.get_raw(key).map(RawValue::into_list)
+    }
+
+    /// Every detail of this object as anytype-heart sent it, keyed by its raw detail key
+    ///
+    /// This is a low level escape hatch for debugging and for details the typed API doesn't
+    /// cover yet. Which keys show up here depends on anytype-heart's version and isn't part of
+    /// this crate's stable API. The name isn't included, see [Object::name]
+    pub fn raw_details(&self) -> BTreeMap<String, RawValue> {
+        self.state()
+            .relations
+            .fields
+            .iter()
+            .map(|(key, value)| {
+                let value = value
+                    .kind
+                    .clone()
+                    .map(RawValue::from)
+                    .unwrap_or(RawValue::Null);
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Get the value of a relation on this object
+    ///
+    /// Object relations are ordered lists, their objects come back in the order they were set in
     pub async fn get(&self, key: &Relation) -> Option<RelationValue> {
-        let kind = self
+        let kind = self.raw_kind(key)?;
+
+        self.value_from_kind(key, kind).await
+    }
+
+    /// The raw value of a relation on this object as it was received
+    pub(crate) fn raw_kind(&self, key: &Relation) -> Option<prost_types::value::Kind> {
+        self.state()
             .relations
             .fields
             .get(&key.relation_key.0)?
             .kind
             // TODO: This clone is a tiny bit sad but quite hard to avoid right now
-            .clone()?;
-
-        // The below expects SHOULD be unreachable because of how the rest of the public API
-        // works the main error we expect to see here is IncorrectKind but because we pass
-        // to this function a whole Relation and the only way to create a Relation is via
-        // the Space APIs that will get the correct Relation and its format, we KNOW that if
-        // that relation exists on some type it MUST have that format.
-        //
-        // The only way I can think of to cause this to trigger is by changing a Relation
-        // format WHILE the code is running, which would be unfortunate but I accept that being broken for now
-        match key.format() {
-            RelationFormat::Text => String::try_from_prost(kind)
-                .map(RelationValue::Text)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Number => f64::try_from_prost(kind)
-                .map(RelationValue::Number)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Date => f64::try_from_prost(kind)
-                .map(|number| DateTime::from_timestamp(number as i64, 0).expect("unreachable"))
-                .map(|datetime| datetime.naive_utc())
-                .map(RelationValue::Date)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Checkbox => bool::try_from_prost(kind)
-                .map(RelationValue::Checkbox)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Url => String::try_from_prost(kind)
-                .map(RelationValue::Url)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Email => String::try_from_prost(kind)
-                .map(RelationValue::Email)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Phone => String::try_from_prost(kind)
-                .map(RelationValue::Phone)
-                .map(Some)
-                .expect("unreachable"),
-            RelationFormat::Object { .. } => {
-                let ids = <Vec<ObjectId>>::try_from_prost(kind).expect("unreachable");
+            .clone()
+    }
+
+    async fn value_from_kind(
+        &self,
+        key: &Relation,
+        kind: prost_types::value::Kind,
+    ) -> Option<RelationValue> {
+        match unresolved_value(key, kind)? {
+            UnresolvedValue::Value(value) => Some(value),
+            UnresolvedValue::Objects(ids) => {
+                // get_objects returns the objects in the order of the stored ids
                 let objects = self
                     .space
                     .get_objects::<ObjectUnresolved>(ids)
@@ -242,25 +560,435 @@ impl Object {
 
                 Some(RelationValue::Object(objects))
             }
-            _ => todo!(),
         }
     }
 
+    /// Watch the recommended relations of this object's type for changes, including ones made by
+    /// other devices
+    ///
+    /// See [Object::watch_relations] for the items of the stream and to watch other relations
+    pub async fn watch(
+        &self,
+    ) -> Result<SubscriptionStream<(Relation, Option<RelationValue>)>, tonic::Status> {
+        let ty = self.ty().await?;
+        let relations = ty
+            .recommended_relations()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.watch_relations(&relations).await
+    }
+
+    /// Watch the given relations of this object for changes, including ones made by other
+    /// devices
+    ///
+    /// Each item is a relation whose value changed along with its new value, or [None] if it was
+    /// cleared. Requires the client's event listener and with it the `background-tasks` feature,
+    /// see [AnytypeClient::without_event_listener](crate::AnytypeClient::without_event_listener)
+    pub async fn watch_relations(
+        &self,
+        relations: &[Relation],
+    ) -> Result<SubscriptionStream<(Relation, Option<RelationValue>)>, tonic::Status> {
+        let Some(events) = &self.space.inner.client.events else {
+            return Err(tonic::Status::failed_precondition(
+                "Watching an object requires the client's event listener",
+            ));
+        };
+        // Listen before subscribing so that no event in between is missed
+        let events = events.subscribe();
+
+        let relations = relations
+            .iter()
+            .map(|relation| (relation.relation_key.0.clone(), relation.clone()))
+            .collect::<HashMap<_, _>>();
+        let subscription = self
+            .space
+            .subscribe_object(self.id, relations.keys().cloned().collect())
+            .await?;
+
+        let watch = Watch {
+            object: self.clone(),
+            relations,
+            events,
+            pending: VecDeque::new(),
+        };
+
+        let stream = futures_util::stream::unfold(watch, |mut watch| async move {
+            let change = watch.next().await?;
+            Some((change, watch))
+        });
+
+        Ok(SubscriptionStream::new(stream, subscription))
+    }
+
+    /// Change the type of this object
+    ///
+    /// Relation values that are set on the object stay on it even if the new type doesn't
+    /// recommend them, which matches how AnyType apps behave
+    pub async fn set_type(&self, ty: &ObjectType) -> Result<(), tonic::Status> {
+        self.space.set_object_type(self.id, ty).await?;
+        self.state_mut().ty = ty.id();
+
+        Ok(())
+    }
+
+    /// Whether this object is marked as a favorite, which pins it to the sidebar of AnyType apps
+    pub fn is_favorite(&self) -> bool {
+        self.get_raw("isFavorite") == Some(RawValue::Bool(true))
+    }
+
+    /// Mark or unmark this object as a favorite
+    pub async fn set_favorite(&self, is_favorite: bool) -> Result<(), tonic::Status> {
+        self.space.set_object_favorite(self.id, is_favorite).await?;
+        self.state_mut()
+            .relations
+            .fields
+            .insert("isFavorite".to_string(), is_favorite.into_prost());
+
+        Ok(())
+    }
+
+    /// The url anytype-heart's gateway serves this object's file at, [None] for objects that
+    /// aren't files or media
+    ///
+    /// File and media objects are only listed when asked for, see
+    /// [Space::list_objects_with_layouts](crate::Space::list_objects_with_layouts)
+    pub fn file_url(&self) -> Option<String> {
+        if !matches!(
+            self.layout,
+            ObjectLayout::File
+                | ObjectLayout::Image
+                | ObjectLayout::Audio
+                | ObjectLayout::Video
+                | ObjectLayout::Pdf
+        ) {
+            return None;
+        }
+
+        // File objects point at their content with fileId, falling back to the object's own id
+        // for files that predate that
+        let cid = match self.get_raw("fileId") {
+            Some(RawValue::String(cid)) if !cid.is_empty() => cid,
+            _ => format!("{}", self.id),
+        };
+
+        let gateway = self.space.inner.info().gateway_url.clone();
+        let scheme = if gateway.contains("://") {
+            ""
+        } else {
+            "http://"
+        };
+
+        Some(format!(
+            "{scheme}{}/file/{cid}",
+            gateway.trim_end_matches('/')
+        ))
+    }
+
+    /// List the versions of this object anytype-heart recorded, newest first
+    pub async fn history(&self) -> Result<Vec<ObjectVersion>, tonic::Status> {
+        self.space.object_versions(self.id).await
+    }
+
+    /// Get a read-only snapshot of this object as it was at the given version
+    pub async fn at_version(
+        &self,
+        version: &ObjectVersion,
+    ) -> Result<ObjectAtVersion, tonic::Status> {
+        self.space.object_at_version(self.id, version).await
+    }
+
+    /// Export this object as a zip archive in the given format
+    pub async fn export(&self, format: ExportFormat) -> Result<Vec<u8>, tonic::Status> {
+        self.space.export_objects(vec![self.id], format).await
+    }
+
+    /// Set the value of a relation on this object, returning its previous value
+    ///
+    /// Concurrent calls to [Object::set], [Object::clear] and [Object::set_raw_detail] on the same
+    /// object (or its clones) are applied one after the other in the order they were made, each
+    /// call's previous value is the value written by the call right before it. This only holds
+    /// for changes made through this process, changes made elsewhere such as by another client or
+    /// device are only reflected once the object is read again
     pub async fn set(
         &self,
         key: &Relation,
         value: RelationValue,
     ) -> Result<Option<RelationValue>, tonic::Status> {
+        let _write = self.writes.lock().await;
         let previous_value = self.get(key).await;
 
+        let (key, value) = key
+            .validate(value, self.space.inner.client.check_well_formed_values)
+            .map_err(|error| tonic::Status::failed_precondition(format!("{error}")))?
+            .into_raw_parts();
+        self.space
+            .set_detail(self.id, key.clone(), value.clone())
+            .await?;
+        self.state_mut().relations.fields.insert(key, value);
+
+        Ok(previous_value)
+    }
+
+    /// Set an arbitrary detail on this object, for details this crate doesn't model yet such as
+    /// `coverId` or `layoutAlign`
+    ///
+    /// Nothing checks that `value` makes sense for `key`, so prefer [Object::set] for relations.
+    /// Details that identify the object or that anytype-heart manages itself, like `id` or
+    /// `type`, are refused
+    pub async fn set_raw_detail(&self, key: &str, value: RawValue) -> Result<(), tonic::Status> {
+        if PROTECTED_DETAILS.contains(&key) {
+            return Err(tonic::Status::invalid_argument(format!(
+                "Detail `{key}` can't be set directly"
+            )));
+        }
+
+        let value = value.into_prost();
+        let _write = self.writes.lock().await;
         self.space
-            .set_relation(
-                self.id,
-                key.validate(value)
-                    .map_err(|error| tonic::Status::failed_precondition(format!("{error}")))?,
-            )
+            .set_detail(self.id, key.to_string(), value.clone())
             .await?;
 
+        let mut state = self.state_mut();
+        match (key, &value.kind) {
+            // The name is kept outside of the relations, see ObjectUnresolved
+            ("name", Some(prost_types::value::Kind::StringValue(name))) => {
+                state.name = name.clone();
+            }
+            _ => {
+                state.relations.fields.insert(key.to_string(), value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unset the value of a relation on this object, returning its previous value
+    pub async fn clear(&self, key: &Relation) -> Result<Option<RelationValue>, tonic::Status> {
+        let _write = self.writes.lock().await;
+        let previous_value = self.get(key).await;
+
+        self.space.clear_relation(self.id, key).await?;
+        // Cleared relations are stored as null
+        self.state_mut()
+            .relations
+            .fields
+            .insert(key.relation_key.0.clone(), RawValue::Null.into_prost());
+
         Ok(previous_value)
     }
+
+    /// The value of the bundled Description relation on this object
+    pub async fn description(&self) -> Result<Option<String>, tonic::Status> {
+        let relation = self.description_relation().await?;
+
+        match self.get(&relation).await {
+            Some(RelationValue::Text(description)) => Ok(Some(description)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Set the bundled Description relation on this object, returning its previous value
+    pub async fn set_description(&self, text: &str) -> Result<Option<String>, tonic::Status> {
+        let relation = self.description_relation().await?;
+
+        match self
+            .set(&relation, RelationValue::Text(text.to_string()))
+            .await?
+        {
+            Some(RelationValue::Text(description)) => Ok(Some(description)),
+            _ => Ok(None),
+        }
+    }
+
+    async fn description_relation(&self) -> Result<Relation, tonic::Status> {
+        self.space
+            .bundled_relation("description")
+            .await?
+            .ok_or_else(|| {
+                tonic::Status::not_found("The bundled Description relation was not found")
+            })
+    }
+}
+
+/// A relation value whose objects, if it's an Object relation, are yet to be fetched
+pub(crate) enum UnresolvedValue {
+    Value(RelationValue),
+    Objects(Vec<ObjectId>),
+}
+
+/// Convert the raw value of a relation into its [RelationValue], short of fetching the objects
+/// of an Object relation so that they can be fetched in one go for many values
+pub(crate) fn unresolved_value(
+    key: &Relation,
+    kind: prost_types::value::Kind,
+) -> Option<UnresolvedValue> {
+    // Cleared relations are stored as null
+    if let prost_types::value::Kind::NullValue(_) = kind {
+        return None;
+    }
+
+    // The below expects SHOULD be unreachable because of how the rest of the public API
+    // works the main error we expect to see here is IncorrectKind but because we pass
+    // to this function a whole Relation and the only way to create a Relation is via
+    // the Space APIs that will get the correct Relation and its format, we KNOW that if
+    // that relation exists on some type it MUST have that format.
+    //
+    // The only way I can think of to cause this to trigger is by changing a Relation
+    // format WHILE the code is running, which would be unfortunate but I accept that being broken for now
+    match key.format() {
+        RelationFormat::Text => String::try_from_prost(kind)
+            .map(RelationValue::Text)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Number => f64::try_from_prost(kind)
+            .map(RelationValue::Number)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Date => f64::try_from_prost(kind)
+            .map(|number| DateTime::from_timestamp(number as i64, 0).expect("unreachable"))
+            .map(RelationValue::DateUtc)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Checkbox => bool::try_from_prost(kind)
+            .map(RelationValue::Checkbox)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Url => String::try_from_prost(kind)
+            .map(RelationValue::Url)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Email => String::try_from_prost(kind)
+            .map(RelationValue::Email)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        RelationFormat::Phone => String::try_from_prost(kind)
+            .map(RelationValue::Phone)
+            .map(UnresolvedValue::Value)
+            .map(Some)
+            .expect("unreachable"),
+        // Imported objects can carry lists with stray values that aren't object ids, those are
+        // skipped rather than losing the whole value
+        RelationFormat::Object { .. } => Some(UnresolvedValue::Objects(
+            RawValue::from(kind)
+                .into_list()
+                .into_iter()
+                .filter_map(|value| match value {
+                    RawValue::String(id) => match id.parse::<ObjectId>() {
+                        Ok(id) => Some(id),
+                        Err(_) => {
+                            tracing::warn!(%id, "skipping an invalid object id in an Object relation");
+                            None
+                        }
+                    },
+                    value => {
+                        tracing::warn!(?value, "skipping a non-id value in an Object relation");
+                        None
+                    }
+                })
+                .collect(),
+        )),
+        // RelationValue can't hold these formats yet
+        RelationFormat::Select | RelationFormat::MultiSelect | RelationFormat::FileOrMedia => None,
+    }
+}
+
+/// Details [Object::set_raw_detail] refuses to touch
+const PROTECTED_DETAILS: &[&str] = &[
+    "id",
+    "spaceId",
+    "type",
+    "layout",
+    "uniqueKey",
+    "relationKey",
+    "creator",
+    "createdDate",
+    "lastModifiedBy",
+    "lastModifiedDate",
+];
+
+struct Watch {
+    object: Object,
+    relations: HashMap<String, Relation>,
+    events: tokio::sync::broadcast::Receiver<Value>,
+    /// Changes from an already received event that haven't been yielded yet
+    pending: VecDeque<(Relation, Option<prost_types::value::Kind>)>,
+}
+
+impl Watch {
+    async fn next(&mut self) -> Option<(Relation, Option<RelationValue>)> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        loop {
+            if let Some((relation, kind)) = self.pending.pop_front() {
+                let value = match kind {
+                    Some(kind) => self.object.value_from_kind(&relation, kind).await,
+                    None => None,
+                };
+                return Some((relation, value));
+            }
+
+            match self.events.recv().await {
+                Ok(event) => self.queue(event),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(id = %self.object.id, skipped, "watch fell behind on events");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    fn queue(&mut self, event: Value) {
+        let id = format!("{}", self.object.id);
+
+        let changes = match event {
+            Value::ObjectDetailsAmend(amend) if amend.id == id => amend
+                .details
+                .into_iter()
+                .map(|detail| (detail.key, detail.value.and_then(|value| value.kind)))
+                .collect::<Vec<_>>(),
+            Value::ObjectDetailsSet(set) if set.id == id => set
+                .details
+                .map(|details| details.fields)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| (key, value.kind))
+                .collect(),
+            Value::ObjectDetailsUnset(unset) if unset.id == id => {
+                unset.keys.into_iter().map(|key| (key, None)).collect()
+            }
+            _ => return,
+        };
+
+        self.pending
+            .extend(changes.into_iter().filter_map(|(key, kind)| {
+                let relation = self.relations.get(&key)?.clone();
+                // Cleared relations are sent as null
+                let kind =
+                    kind.filter(|kind| !matches!(kind, prost_types::value::Kind::NullValue(_)));
+                Some((relation, kind))
+            }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_layout_keeps_unknown_layouts_as_other() {
+        assert_eq!(
+            ObjectLayout::from_raw(Layout::Bookmark as i32),
+            ObjectLayout::Bookmark
+        );
+        assert_eq!(ObjectLayout::from_raw(9999), ObjectLayout::Other(9999));
+        assert_eq!(ObjectLayout::Other(9999).into_raw(), 9999);
+    }
 }
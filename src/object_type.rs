@@ -1,36 +1,164 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Display,
+    str::FromStr,
 };
 
 use crate::{
-    object::ObjectId,
+    object::{Object, ObjectId, ParseObjectIdError},
     prost_ext::{IntoProstValue, ProstConversionError, ProstStruct, TryFromProst},
     relation::{Relation, RelationId, RelationSpec},
     unique_key::UniqueKey,
     Space,
 };
 
+#[derive(Default)]
 pub struct ObjectTypeSpec {
     /// The name of the object type
     pub name: String,
     pub recommended_relations: BTreeSet<RelationSpec>,
+    /// An emoji to use as the object type's icon
+    pub icon: Option<String>,
+    /// The color of the object type's icon, AnyType apps offer colors from 1 to 10
+    pub color: Option<u8>,
 }
 
 impl ObjectTypeSpec {
+    pub fn new(
+        name: impl Into<String>,
+        recommended_relations: impl IntoIterator<Item = RelationSpec>,
+    ) -> Self {
+        ObjectTypeSpec {
+            name: name.into(),
+            recommended_relations: recommended_relations.into_iter().collect(),
+            icon: None,
+            color: None,
+        }
+    }
+
+    /// Start building an object type spec one relation at a time, for specs whose relations are
+    /// only known piece by piece
+    pub fn builder(name: impl Into<String>) -> ObjectTypeSpecBuilder {
+        ObjectTypeSpecBuilder {
+            spec: ObjectTypeSpec::new(name, []),
+        }
+    }
+
     pub(crate) fn to_struct(&self, relations: Vec<RelationId>) -> prost_types::Struct {
-        prost_types::Struct {
-            fields: BTreeMap::from([
-                ("name".to_string(), self.name.clone().into_prost()),
-                (
-                    "recommendedRelations".to_string(),
-                    relations
-                        .into_iter()
-                        .map(|relation_id| relation_id.into_prost())
-                        .collect::<Vec<_>>()
-                        .into_prost(),
-                ),
-            ]),
+        let mut fields = BTreeMap::from([
+            ("name".to_string(), self.name.clone().into_prost()),
+            (
+                "recommendedRelations".to_string(),
+                relations
+                    .into_iter()
+                    .map(|relation_id| relation_id.into_prost())
+                    .collect::<Vec<_>>()
+                    .into_prost(),
+            ),
+        ]);
+
+        if let Some(icon) = &self.icon {
+            fields.insert("iconEmoji".to_string(), icon.clone().into_prost());
+        }
+        if let Some(color) = self.color {
+            fields.insert("iconOption".to_string(), f64::from(color).into_prost());
+        }
+
+        prost_types::Struct { fields }
+    }
+}
+
+/// Builds an [ObjectTypeSpec], see [ObjectTypeSpec::builder]
+pub struct ObjectTypeSpecBuilder {
+    spec: ObjectTypeSpec,
+}
+
+impl ObjectTypeSpecBuilder {
+    /// Recommend a relation on the object type
+    pub fn relation(mut self, relation: RelationSpec) -> Self {
+        self.spec.recommended_relations.insert(relation);
+        self
+    }
+
+    /// Recommend every relation in `relations` on the object type
+    pub fn relations(mut self, relations: impl IntoIterator<Item = RelationSpec>) -> Self {
+        self.spec.recommended_relations.extend(relations);
+        self
+    }
+
+    /// Use an emoji as the object type's icon
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.spec.icon = Some(icon.into());
+        self
+    }
+
+    /// Color the object type's icon, AnyType apps offer colors from 1 to 10
+    pub fn color(mut self, color: u8) -> Self {
+        self.spec.color = Some(color);
+        self
+    }
+
+    pub fn build(self) -> ObjectTypeSpec {
+        self.spec
+    }
+}
+
+/// Object types anytype-heart ships with every space, see [Space::bundled_type]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BundledType {
+    Page,
+    Note,
+    Task,
+    Bookmark,
+    Collection,
+    Set,
+}
+
+impl BundledType {
+    /// The type's `uniqueKey`, which unlike its name is the same regardless of the user's language
+    /// or renames
+    pub fn unique_key(&self) -> &'static str {
+        match self {
+            BundledType::Page => "ot-page",
+            BundledType::Note => "ot-note",
+            BundledType::Task => "ot-task",
+            BundledType::Bookmark => "ot-bookmark",
+            BundledType::Collection => "ot-collection",
+            BundledType::Set => "ot-set",
+        }
+    }
+}
+
+/// The sections AnyType apps lay an object type's relations out in
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelationGroup {
+    /// Relations shown in the object's header, under its name
+    Featured,
+    /// Relations shown in the object's relations panel, these are the type's
+    /// [recommended relations](ObjectType::recommended_relations)
+    Sidebar,
+    /// Relations kept on the type but hidden from the object's relations panel
+    Hidden,
+    /// Relations describing file objects' files, such as their size or extension
+    File,
+}
+
+impl RelationGroup {
+    /// Every group, in the order AnyType apps show them
+    pub(crate) const ALL: [RelationGroup; 4] = [
+        RelationGroup::Featured,
+        RelationGroup::Sidebar,
+        RelationGroup::Hidden,
+        RelationGroup::File,
+    ];
+
+    /// The detail of the object type that lists the relations of this group
+    pub(crate) fn detail_key(&self) -> &'static str {
+        match self {
+            RelationGroup::Featured => "recommendedFeaturedRelations",
+            RelationGroup::Sidebar => "recommendedRelations",
+            RelationGroup::Hidden => "recommendedHiddenRelations",
+            RelationGroup::File => "recommendedFileRelations",
         }
     }
 }
@@ -58,6 +186,14 @@ impl TryFromProst for ObjectTypeId {
     }
 }
 
+impl FromStr for ObjectTypeId {
+    type Err = ParseObjectIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(ObjectTypeId)
+    }
+}
+
 impl From<ObjectTypeId> for ObjectId {
     fn from(value: ObjectTypeId) -> Self {
         value.0
@@ -66,9 +202,13 @@ impl From<ObjectTypeId> for ObjectId {
 
 pub(crate) struct ObjectTypeUnresolved {
     id: ObjectTypeId,
-    name: String,
-    unique_key: UniqueKey,
+    pub(crate) name: String,
+    pub(crate) unique_key: UniqueKey,
     pub(crate) recommended_relations: BTreeSet<RelationId>,
+    icon: Option<String>,
+    color: Option<u8>,
+    default_template: Option<ObjectId>,
+    relation_groups: Vec<(RelationGroup, Vec<RelationId>)>,
 }
 
 impl TryFromProst for ObjectTypeUnresolved {
@@ -88,13 +228,48 @@ impl TryFromProst for ObjectTypeUnresolved {
         let id = value.take::<ObjectTypeId>("id")?;
         let name = value.take::<String>("name")?;
         let unique_key = value.take::<UniqueKey>("uniqueKey")?;
-        let recommended_relations = value.take::<BTreeSet<RelationId>>("recommendedRelations")?;
+        // Some types, system ones in particular, don't have any of the groups' fields at all
+        let mut relation_groups = Vec::new();
+        for group in RelationGroup::ALL {
+            let relations = value
+                .take_optional::<Vec<RelationId>>(group.detail_key())?
+                .unwrap_or_default();
+            if !relations.is_empty() {
+                relation_groups.push((group, relations));
+            }
+        }
+        let recommended_relations = relation_groups
+            .iter()
+            .find(|(group, _)| *group == RelationGroup::Sidebar)
+            .map(|(_, relations)| relations.iter().copied().collect())
+            .unwrap_or_default();
+        // AnyType stores a missing icon as an empty string and a missing color as 0
+        let icon = value
+            .take_optional::<String>("iconEmoji")?
+            .filter(|icon| !icon.is_empty());
+        let color = value
+            .take_optional::<f64>("iconOption")?
+            .map(|color| color as u8)
+            .filter(|color| *color != 0);
+        // And a missing default template as an empty string too
+        let default_template = value
+            .take_optional::<String>("defaultTemplateId")?
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse::<ObjectId>()
+                    .map_err(|ParseObjectIdError(id)| ProstConversionError::InvalidCid(id))
+            })
+            .transpose()?;
 
         Ok(Self {
             id,
             name,
             unique_key,
             recommended_relations,
+            icon,
+            color,
+            default_template,
+            relation_groups,
         })
     }
 }
@@ -103,6 +278,10 @@ impl crate::space::SearchOutput for ObjectTypeUnresolved {
     const LAYOUT: &'static [crate::pb::models::object_type::Layout] =
         &[crate::pb::models::object_type::Layout::ObjectType];
     type Id = ObjectTypeId;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
 }
 
 impl ObjectTypeUnresolved {
@@ -112,6 +291,10 @@ impl ObjectTypeUnresolved {
             name: self.name,
             unique_key: self.unique_key,
             recommended_relations,
+            icon: self.icon,
+            color: self.color,
+            default_template: self.default_template,
+            relation_groups: self.relation_groups,
         }
     }
 
@@ -132,6 +315,10 @@ impl ObjectTypeUnresolved {
             name: self.name,
             unique_key: self.unique_key,
             recommended_relations,
+            icon: self.icon,
+            color: self.color,
+            default_template: self.default_template,
+            relation_groups: self.relation_groups,
         })
     }
 }
@@ -142,6 +329,17 @@ pub struct ObjectType {
     name: String,
     pub(crate) unique_key: UniqueKey,
     recommended_relations: BTreeSet<Relation>,
+    icon: Option<String>,
+    color: Option<u8>,
+    pub(crate) default_template: Option<ObjectId>,
+    relation_groups: Vec<(RelationGroup, Vec<RelationId>)>,
+}
+
+/// Formats as the object type's name
+impl Display for ObjectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
 }
 
 impl ObjectType {
@@ -156,4 +354,168 @@ impl ObjectType {
     pub fn recommended_relations(&self) -> &BTreeSet<Relation> {
         &self.recommended_relations
     }
+
+    /// The emoji used as this object type's icon
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// The color of this object type's icon, from 1 to 10
+    pub fn color(&self) -> Option<u8> {
+        self.color
+    }
+
+    /// List the templates objects of this type can be created from, see
+    /// [Space::create_object_from_template]
+    pub async fn templates(&self, space: &Space) -> Result<Vec<Object>, tonic::Status> {
+        space.templates(self).await
+    }
+
+    /// Create an empty template for objects of this type, fill it in like any other object
+    pub async fn create_template(
+        &self,
+        space: &Space,
+        name: &str,
+    ) -> Result<Object, tonic::Status> {
+        space.create_template(self, name).await
+    }
+
+    /// The relations of this type grouped into the sections AnyType apps show them in, in the
+    /// order they're shown. Groups without any relations are left out
+    pub async fn relation_groups(
+        &self,
+        space: &Space,
+    ) -> Result<Vec<(RelationGroup, Vec<Relation>)>, tonic::Status> {
+        let ids = self
+            .relation_groups
+            .iter()
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .collect::<BTreeSet<_>>();
+        let relations = space
+            .get_objects::<Relation>(ids)
+            .await?
+            .into_iter()
+            .map(|relation| (relation.id(), relation))
+            .collect::<HashMap<_, _>>();
+
+        Ok(self
+            .relation_groups
+            .iter()
+            .map(|(group, ids)| {
+                // Relations that were deleted since are skipped
+                let group_relations = ids
+                    .iter()
+                    .filter_map(|id| relations.get(id).cloned())
+                    .collect();
+                (*group, group_relations)
+            })
+            .collect())
+    }
+
+    /// The template the AnyType apps pre-fill new objects of this type from, if one was chosen
+    pub async fn default_template(&self, space: &Space) -> Result<Option<Object>, tonic::Status> {
+        let Some(id) = self.default_template else {
+            return Ok(None);
+        };
+
+        Ok(space.get_objects_by_id([id]).await?.pop())
+    }
+
+    /// Choose the template new objects of this type are pre-filled from
+    ///
+    /// This object type keeps the default template it was fetched with, get the type again to
+    /// create objects from the new one
+    pub async fn set_default_template(
+        &self,
+        space: &Space,
+        template: &Object,
+    ) -> Result<(), tonic::Status> {
+        space
+            .set_detail(
+                self.id.into(),
+                "defaultTemplateId".to_string(),
+                template.id().into_prost(),
+            )
+            .await
+    }
+}
+
+impl From<ObjectType> for ObjectTypeSpec {
+    fn from(value: ObjectType) -> Self {
+        ObjectTypeSpec {
+            name: value.name,
+            recommended_relations: value
+                .recommended_relations
+                .into_iter()
+                .map(RelationSpec::from)
+                .collect(),
+            icon: value.icon,
+            color: value.color,
+        }
+    }
+}
+
+impl From<&ObjectType> for ObjectTypeSpec {
+    fn from(value: &ObjectType) -> Self {
+        ObjectTypeSpec {
+            name: value.name.clone(),
+            recommended_relations: value
+                .recommended_relations
+                .iter()
+                .map(RelationSpec::from)
+                .collect(),
+            icon: value.icon.clone(),
+            color: value.color,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::models::object_type::Layout;
+    use crate::raw_value::RawValue;
+
+    fn type_details(
+        extra: impl IntoIterator<Item = (&'static str, prost_types::Value)>,
+    ) -> prost_types::Struct {
+        let mut fields = BTreeMap::from([
+            (
+                "layout".to_string(),
+                f64::from(Layout::ObjectType as i32).into_prost(),
+            ),
+            (
+                "id".to_string(),
+                "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+                    .to_string()
+                    .into_prost(),
+            ),
+            ("name".to_string(), "Bare".to_string().into_prost()),
+            ("uniqueKey".to_string(), "ot-bare".to_string().into_prost()),
+        ]);
+        fields.extend(
+            extra
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value)),
+        );
+
+        prost_types::Struct { fields }
+    }
+
+    #[test]
+    fn object_type_without_recommended_relations_resolves() {
+        let object_type = ObjectTypeUnresolved::try_from_prost(type_details([])).unwrap();
+        assert!(object_type.recommended_relations.is_empty());
+        assert!(object_type.relation_groups.is_empty());
+    }
+
+    #[test]
+    fn object_type_with_cleared_recommended_relations_resolves() {
+        let object_type = ObjectTypeUnresolved::try_from_prost(type_details([(
+            "recommendedRelations",
+            RawValue::Null.into_prost(),
+        )]))
+        .unwrap();
+        assert!(object_type.recommended_relations.is_empty());
+    }
 }
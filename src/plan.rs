@@ -0,0 +1,32 @@
+use crate::relation::RelationSpec;
+
+/// What an `obtain_*` call would create in a space, computed without creating it
+///
+/// `obtain_*` calls never change anything that already exists, they return what they find as is
+/// or fail where it conflicts with the spec. So this only lists what's missing and would be
+/// created, there are no field changes to list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreationPlan {
+    /// Relations that don't exist yet and would be created
+    pub relations_to_create: Vec<RelationSpec>,
+    /// Names of object types that don't exist yet and would be created
+    pub object_types_to_create: Vec<String>,
+    /// Names of objects that don't exist yet and would be created
+    pub objects_to_create: Vec<String>,
+}
+
+impl CreationPlan {
+    /// Whether applying this plan wouldn't create anything
+    pub fn is_empty(&self) -> bool {
+        self.relations_to_create.is_empty()
+            && self.object_types_to_create.is_empty()
+            && self.objects_to_create.is_empty()
+    }
+
+    pub(crate) fn extend(&mut self, other: CreationPlan) {
+        self.relations_to_create.extend(other.relations_to_create);
+        self.object_types_to_create
+            .extend(other.object_types_to_create);
+        self.objects_to_create.extend(other.objects_to_create);
+    }
+}
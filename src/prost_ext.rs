@@ -174,6 +174,7 @@ pub(crate) enum ProstConversionError {
         received: ProstKind,
     },
     InvalidEnumValue(i32),
+    InvalidCid(String),
 }
 
 impl Display for ProstConversionError {
@@ -195,6 +196,10 @@ impl Display for ProstConversionError {
                 f.write_str("Enum value invalid ")?;
                 value.fmt(f)
             }
+            ProstConversionError::InvalidCid(value) => {
+                f.write_str("Invalid CID ")?;
+                f.write_str(value)
+            }
         }
     }
 }
@@ -239,6 +244,8 @@ impl ProstStruct {
         T: TryFromProst<Input = prost_types::value::Kind>,
     {
         match self.take_inner(field) {
+            // anytype-heart clears a detail by setting it to null, which is as good as missing
+            Ok(prost_types::value::Kind::NullValue(_)) => Ok(None),
             Ok(kind) => T::try_from_prost(kind).map(Some),
             Err(ProstConversionError::MissingStructField(_)) => Ok(None),
             Err(error) => Err(error),
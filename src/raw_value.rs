@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use crate::prost_ext::IntoProstValue;
+
+/// A detail value as anytype-heart stores it, without any knowledge of the relation format it
+/// belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub enum RawValue {
+    Null,
+    String(String),
+    Number(f64),
+    Bool(bool),
+    List(Vec<RawValue>),
+    Struct(BTreeMap<String, RawValue>),
+}
+
+impl IntoProstValue for RawValue {
+    fn into_prost(self) -> prost_types::Value {
+        use prost_types::value::Kind;
+
+        match self {
+            RawValue::Null => prost_types::Value {
+                kind: Some(Kind::NullValue(prost_types::NullValue::NullValue.into())),
+            },
+            RawValue::String(string) => string.into_prost(),
+            RawValue::Number(number) => number.into_prost(),
+            RawValue::Bool(boolean) => boolean.into_prost(),
+            RawValue::List(values) => values
+                .into_iter()
+                .map(RawValue::into_prost)
+                .collect::<Vec<_>>()
+                .into_prost(),
+            RawValue::Struct(fields) => prost_types::Value {
+                kind: Some(Kind::StructValue(prost_types::Struct {
+                    fields: fields
+                        .into_iter()
+                        .map(|(key, value)| (key, value.into_prost()))
+                        .collect(),
+                })),
+            },
+        }
+    }
+}
+
+impl RawValue {
+    /// The elements of a list value whatever their kinds, for lists that are expected to be
+    /// homogeneous but may not be, such as in imported data
+    ///
+    /// Null reads as an empty list and any other value as a list of itself, matching how
+    /// anytype-heart treats single values stored in list relations
+    pub(crate) fn into_list(self) -> Vec<RawValue> {
+        match self {
+            RawValue::List(values) => values,
+            RawValue::Null => Vec::new(),
+            value => vec![value],
+        }
+    }
+}
+
+impl From<prost_types::value::Kind> for RawValue {
+    fn from(kind: prost_types::value::Kind) -> Self {
+        use prost_types::value::Kind;
+
+        match kind {
+            Kind::NullValue(_) => RawValue::Null,
+            Kind::StringValue(string) => RawValue::String(string),
+            Kind::NumberValue(number) => RawValue::Number(number),
+            Kind::BoolValue(boolean) => RawValue::Bool(boolean),
+            Kind::ListValue(list) => RawValue::List(
+                list.values
+                    .into_iter()
+                    .map(|value| value.kind.map(RawValue::from).unwrap_or(RawValue::Null))
+                    .collect(),
+            ),
+            Kind::StructValue(fields) => RawValue::Struct(
+                fields
+                    .fields
+                    .into_iter()
+                    .map(|(key, value)| {
+                        (
+                            key,
+                            value.kind.map(RawValue::from).unwrap_or(RawValue::Null),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
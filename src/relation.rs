@@ -1,15 +1,17 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Display,
+    str::FromStr,
 };
 
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 use crate::{
-    object::{Object, ObjectId},
-    object_type::ObjectTypeId,
+    object::{Object, ObjectId, ParseObjectIdError},
+    object_type::{ObjectType, ObjectTypeId},
     pb::models::RelationFormat as InternalRelationFormat,
     prost_ext::{IntoProstValue, ProstConversionError, ProstStruct, TryFromProst},
+    space::Space,
 };
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -19,8 +21,89 @@ pub struct RelationSpec {
     pub format: RelationFormat,
 }
 
-impl From<RelationSpec> for prost_types::Struct {
-    fn from(value: RelationSpec) -> Self {
+pub struct RelationDescription {
+    pub name: String,
+    pub format: RelationFormat,
+    pub description: Option<String>,
+    /// The maximum amount of values this relation can hold, only meaningful for Object and
+    /// MultiSelect relations. `None` means there is no limit
+    pub max_count: Option<u32>,
+    /// Whether AnyType apps show and pick a time along with the date, only meaningful for Date
+    /// relations
+    pub include_time: bool,
+}
+
+impl RelationSpec {
+    pub fn new(name: impl Into<String>, format: RelationFormat) -> Self {
+        RelationSpec {
+            name: name.into(),
+            format,
+        }
+    }
+
+    pub fn text(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Text)
+    }
+
+    pub fn number(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Number)
+    }
+
+    pub fn select(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Select)
+    }
+
+    pub fn multi_select(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::MultiSelect)
+    }
+
+    pub fn date(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Date)
+    }
+
+    pub fn file_or_media(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::FileOrMedia)
+    }
+
+    pub fn checkbox(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Checkbox)
+    }
+
+    pub fn url(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Url)
+    }
+
+    pub fn email(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Email)
+    }
+
+    pub fn phone(name: impl Into<String>) -> Self {
+        Self::new(name, RelationFormat::Phone)
+    }
+
+    /// An Object relation that only accepts objects of the given types
+    pub fn object(name: impl Into<String>, types: impl IntoIterator<Item = ObjectTypeId>) -> Self {
+        Self::new(
+            name,
+            RelationFormat::Object {
+                types: types.into_iter().collect(),
+            },
+        )
+    }
+
+    pub fn as_description(&self) -> RelationDescription {
+        RelationDescription {
+            name: self.name.clone(),
+            format: self.format.clone(),
+            description: None,
+            max_count: None,
+            include_time: false,
+        }
+    }
+}
+
+impl From<RelationDescription> for prost_types::Struct {
+    fn from(value: RelationDescription) -> Self {
         let mut fields = BTreeMap::from([
             ("name".to_string(), value.name.to_string().into_prost()),
             (
@@ -29,6 +112,21 @@ impl From<RelationSpec> for prost_types::Struct {
             ),
         ]);
 
+        if let Some(description) = value.description {
+            fields.insert("description".to_string(), description.into_prost());
+        }
+
+        if let Some(max_count) = value.max_count {
+            fields.insert(
+                "relationMaxCount".to_string(),
+                f64::from(max_count).into_prost(),
+            );
+        }
+
+        if value.include_time {
+            fields.insert("relationFormatIncludeTime".to_string(), true.into_prost());
+        }
+
         if let RelationFormat::Object { types } = value.format {
             fields.insert(
                 "relationFormatObjectTypes".to_string(),
@@ -110,6 +208,52 @@ impl Display for RelationFormat {
     }
 }
 
+/// Error returned when parsing a [RelationFormat] from a string fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRelationFormatError {
+    /// The string isn't the name of any relation format
+    Unknown(String),
+    /// Object relations need the object types they accept, which a bare name can't carry
+    MissingObjectTypes,
+}
+
+impl Display for ParseRelationFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRelationFormatError::Unknown(format) => {
+                write!(f, "Unknown relation format `{format}`")
+            }
+            ParseRelationFormatError::MissingObjectTypes => f.write_str(
+                "Object relation format can't be parsed without the object types it accepts",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseRelationFormatError {}
+
+/// Parses the names [RelationFormat]'s [Display] implementation produces, ignoring case
+impl FromStr for RelationFormat {
+    type Err = ParseRelationFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(RelationFormat::Text),
+            "number" => Ok(RelationFormat::Number),
+            "select" => Ok(RelationFormat::Select),
+            "multiselect" => Ok(RelationFormat::MultiSelect),
+            "date" => Ok(RelationFormat::Date),
+            "fileormedia" => Ok(RelationFormat::FileOrMedia),
+            "checkbox" => Ok(RelationFormat::Checkbox),
+            "url" => Ok(RelationFormat::Url),
+            "email" => Ok(RelationFormat::Email),
+            "phone" => Ok(RelationFormat::Phone),
+            "object" => Err(ParseRelationFormatError::MissingObjectTypes),
+            _ => Err(ParseRelationFormatError::Unknown(s.to_string())),
+        }
+    }
+}
+
 impl RelationFormat {
     fn from_internal(
         internal: InternalRelationFormat,
@@ -183,6 +327,14 @@ impl TryFromProst for RelationId {
     }
 }
 
+impl FromStr for RelationId {
+    type Err = ParseObjectIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(RelationId)
+    }
+}
+
 impl From<RelationId> for ObjectId {
     fn from(value: RelationId) -> Self {
         value.0
@@ -203,14 +355,23 @@ impl TryFromProst for RelationKey {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum RelationValue {
     Text(String),
     Number(f64),
     // TODO:
     // Select
     // MultiSelect
+    /// A date taken to be in UTC, values read from anytype-heart are always
+    /// [RelationValue::DateUtc] instead, so code matching on this variant should move to
+    /// [RelationValue::as_datetime] which reads both
+    #[deprecated(
+        note = "values read from anytype-heart now come back as RelationValue::DateUtc and never \
+                match this variant, match on DateUtc or use RelationValue::as_datetime instead"
+    )]
     Date(NaiveDateTime),
+    /// A date, AnyType stores dates as unix timestamps with a precision of seconds
+    DateUtc(DateTime<Utc>),
     // FileOrMedia
     Checkbox(bool),
     Url(String),
@@ -224,18 +385,134 @@ impl RelationValue {
         match self {
             RelationValue::Text(_) => RelationFormat::Text,
             RelationValue::Number(_) => RelationFormat::Number,
-            RelationValue::Date(_) => RelationFormat::Date,
+            #[allow(deprecated)]
+            RelationValue::Date(_) | RelationValue::DateUtc(_) => RelationFormat::Date,
             RelationValue::Checkbox(_) => RelationFormat::Checkbox,
             RelationValue::Url(_) => RelationFormat::Url,
             RelationValue::Email(_) => RelationFormat::Email,
             RelationValue::Phone(_) => RelationFormat::Phone,
             RelationValue::Object(objects) => RelationFormat::Object {
-                types: objects
-                    .clone()
-                    .into_iter()
-                    .map(|object| object.ty)
-                    .collect(),
+                types: objects.iter().map(Object::type_id).collect(),
+            },
+        }
+    }
+
+    /// The string held by Url, Email and Phone values that don't look like one
+    ///
+    /// These checks are deliberately loose and only catch values that are clearly wrong
+    fn malformed(&self) -> Option<&str> {
+        let (string, well_formed) = match self {
+            RelationValue::Url(url) => {
+                let well_formed = !url.contains(char::is_whitespace)
+                    && url.split_once("://").is_some_and(|(scheme, rest)| {
+                        !scheme.is_empty()
+                            && scheme
+                                .chars()
+                                .all(|char| char.is_ascii_alphanumeric() || "+-.".contains(char))
+                            && !rest.is_empty()
+                    });
+                (url, well_formed)
+            }
+            RelationValue::Email(email) => {
+                let well_formed = !email.contains(char::is_whitespace)
+                    && email.split_once('@').is_some_and(|(local, domain)| {
+                        !local.is_empty() && !domain.is_empty() && !domain.contains('@')
+                    });
+                (email, well_formed)
+            }
+            RelationValue::Phone(phone) => {
+                let well_formed = phone.chars().any(|char| char.is_ascii_digit())
+                    && phone
+                        .chars()
+                        .all(|char| char.is_ascii_digit() || "+-(). ".contains(char));
+                (phone, well_formed)
+            }
+            _ => return None,
+        };
+
+        (!well_formed).then_some(string.as_str())
+    }
+
+    /// The date stored in this value, whichever of [RelationValue::DateUtc] or the deprecated
+    /// [RelationValue::Date] holds it
+    ///
+    /// Prefer this over matching on the variants, values read from anytype-heart are always
+    /// [RelationValue::DateUtc] while values built by callers may still be the naive variant
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            RelationValue::DateUtc(datetime) => Some(*datetime),
+            #[allow(deprecated)]
+            RelationValue::Date(datetime) => Some(datetime.and_utc()),
+            _ => None,
+        }
+    }
+
+    /// The number stored in this value, `None` for values that aren't [RelationValue::Number]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            RelationValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// The number stored in this value if it's a whole number that fits in an [i64]
+    ///
+    /// AnyType stores all numbers as floats, so this is the way to read numbers meant as counts
+    pub fn as_i64(&self) -> Option<i64> {
+        let number = self.as_f64()?;
+
+        // `i64::MAX as f64` rounds up to 2^63 which is already out of range, hence the strict
+        // comparison against it
+        if number.fract() != 0.0 || number < i64::MIN as f64 || number >= i64::MAX as f64 {
+            return None;
+        }
+
+        Some(number as i64)
+    }
+
+    /// Parse a string into a value of the given format, for importing from sources where every
+    /// field is a string
+    ///
+    /// Numbers are parsed as floats, dates as RFC 3339 or `YYYY-MM-DD` optionally followed by a
+    /// `HH:MM:SS` time in UTC and checkboxes as `true`/`false` or `yes`/`no` ignoring case. Text,
+    /// Url, Email and Phone values are taken as is
+    pub fn coerce_to(format: &RelationFormat, raw: &str) -> Result<RelationValue, CoerceError> {
+        let invalid = || CoerceError::Invalid {
+            format: format.clone(),
+            raw: raw.to_string(),
+        };
+        let trimmed = raw.trim();
+
+        match format {
+            RelationFormat::Text => Ok(RelationValue::Text(raw.to_string())),
+            RelationFormat::Url => Ok(RelationValue::Url(raw.to_string())),
+            RelationFormat::Email => Ok(RelationValue::Email(raw.to_string())),
+            RelationFormat::Phone => Ok(RelationValue::Phone(raw.to_string())),
+            RelationFormat::Number => trimmed
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .map(RelationValue::Number)
+                .ok_or_else(invalid),
+            RelationFormat::Date => DateTime::parse_from_rfc3339(trimmed)
+                .map(|datetime| datetime.naive_utc())
+                .or_else(|_| NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%dT%H:%M:%S"))
+                .or_else(|_| NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S"))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                        .map(|date| date.and_time(NaiveTime::MIN))
+                })
+                .map(|datetime| RelationValue::DateUtc(datetime.and_utc()))
+                .map_err(|_| invalid()),
+            RelationFormat::Checkbox => match trimmed.to_ascii_lowercase().as_str() {
+                "true" | "yes" => Ok(RelationValue::Checkbox(true)),
+                "false" | "no" => Ok(RelationValue::Checkbox(false)),
+                _ => Err(invalid()),
             },
+            RelationFormat::Select
+            | RelationFormat::MultiSelect
+            | RelationFormat::FileOrMedia
+            | RelationFormat::Object { .. } => Err(CoerceError::Unsupported(format.clone())),
         }
     }
 }
@@ -248,7 +525,9 @@ impl IntoProstValue for RelationValue {
             | RelationValue::Email(string)
             | RelationValue::Phone(string) => string.into_prost(),
             RelationValue::Number(number) => number.into_prost(),
+            #[allow(deprecated)]
             RelationValue::Date(datetime) => (datetime.and_utc().timestamp() as f64).into_prost(),
+            RelationValue::DateUtc(datetime) => (datetime.timestamp() as f64).into_prost(),
             RelationValue::Checkbox(boolean) => boolean.into_prost(),
             RelationValue::Object(objects) => objects
                 .into_iter()
@@ -259,27 +538,86 @@ impl IntoProstValue for RelationValue {
     }
 }
 
-pub struct IncompatibleRelationValue {
-    expected: RelationFormat,
-    received: RelationFormat,
+#[derive(Debug)]
+pub enum InvalidRelationValue {
+    IncompatibleFormat {
+        expected: RelationFormat,
+        received: RelationFormat,
+    },
+    /// Number relations can only hold finite numbers
+    NonFiniteNumber(f64),
+    /// A Url, Email or Phone value that doesn't look like one, only checked when the client was
+    /// built with [AnytypeClient::with_well_formed_values](crate::AnytypeClient::with_well_formed_values)
+    MalformedValue {
+        format: RelationFormat,
+        value: String,
+    },
+    /// More objects than the relation's [Relation::max_count] allows
+    TooManyValues { max: u32, got: usize },
 }
 
-impl Display for IncompatibleRelationValue {
+impl Display for InvalidRelationValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Expected format doesn't match received format:\nexpected:{}\nreceived:{}",
-            self.expected, self.received
-        )
+        match self {
+            InvalidRelationValue::IncompatibleFormat { expected, received } => write!(
+                f,
+                "Expected format doesn't match received format:\nexpected:{}\nreceived:{}",
+                expected, received
+            ),
+            InvalidRelationValue::NonFiniteNumber(number) => {
+                write!(f, "Expected a finite number but received {number}")
+            }
+            InvalidRelationValue::MalformedValue { format, value } => {
+                write!(f, "`{value}` is not a well formed {format}")
+            }
+            InvalidRelationValue::TooManyValues { max, got } => {
+                write!(f, "Expected at most {max} values but received {got}")
+            }
+        }
     }
 }
 
+impl std::error::Error for InvalidRelationValue {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoerceError {
+    /// The string doesn't parse as a value of the format
+    Invalid { format: RelationFormat, raw: String },
+    /// Values of the format can't be made from a string alone
+    Unsupported(RelationFormat),
+}
+
+impl Display for CoerceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoerceError::Invalid { format, raw } => {
+                write!(f, "`{raw}` can't be read as a {format}")
+            }
+            CoerceError::Unsupported(format) => {
+                write!(f, "{format} values can't be made from a string")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoerceError {}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Relation {
     id: RelationId,
     name: String,
     pub(crate) relation_key: RelationKey,
     format: RelationFormat,
+    description: Option<String>,
+    max_count: Option<u32>,
+    include_time: bool,
+}
+
+/// Formats as the relation's name followed by its format, like `Due date (Date)`
+impl Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.format)
+    }
 }
 
 impl Relation {
@@ -299,6 +637,20 @@ impl Relation {
         &self.format
     }
 
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The maximum amount of values this relation can hold, `None` means there is no limit
+    pub fn max_count(&self) -> Option<u32> {
+        self.max_count
+    }
+
+    /// Whether AnyType apps show and pick a time along with the date of this Date relation
+    pub fn include_time(&self) -> bool {
+        self.include_time
+    }
+
     pub fn into_spec(self) -> RelationSpec {
         RelationSpec {
             name: self.name,
@@ -313,23 +665,155 @@ impl Relation {
         }
     }
 
+    /// Change the format of this relation, returning the updated relation
+    ///
+    /// Only changes that keep existing values valid are allowed, which are changes between the
+    /// string based formats (Text, Url, Email and Phone) and changes to the allowed types of
+    /// an Object relation. Any other change fails with [FormatChangeUnsupported]
+    pub async fn change_format(
+        &self,
+        space: &Space,
+        new: RelationFormat,
+    ) -> Result<Relation, tonic::Status> {
+        if !self.format.can_change_to(&new) {
+            return Err(FormatChangeUnsupported {
+                from: self.format.clone(),
+                to: new,
+            }
+            .into());
+        }
+
+        let id = ObjectId::from(self.id);
+        space
+            .set_detail(
+                id,
+                "relationFormat".to_string(),
+                f64::from(&new).into_prost(),
+            )
+            .await?;
+
+        if let RelationFormat::Object { types } = &new {
+            space
+                .set_detail(
+                    id,
+                    "relationFormatObjectTypes".to_string(),
+                    types
+                        .iter()
+                        .map(|object_id| object_id.into_prost())
+                        .collect::<Vec<_>>()
+                        .into_prost(),
+                )
+                .await?;
+        }
+
+        Ok(Relation {
+            format: new,
+            ..self.clone()
+        })
+    }
+
+    /// List the object types that recommend this relation, the inverse of
+    /// [ObjectType::recommended_relations]
+    ///
+    /// Useful for checking nothing depends on a relation before deleting it
+    pub async fn used_by_types(&self, space: &Space) -> Result<Vec<ObjectType>, tonic::Status> {
+        space.object_types_recommending(self).await
+    }
+
+    /// Check that a value fits this relation, and when `check_well_formed` is set that Url, Email
+    /// and Phone values look like one
     pub(crate) fn validate(
         &self,
         value: RelationValue,
-    ) -> Result<RelationDetail, IncompatibleRelationValue> {
+        check_well_formed: bool,
+    ) -> Result<RelationDetail, InvalidRelationValue> {
         let expected_format = self.format();
         let received_format = value.format();
-        if expected_format.is_superset(&received_format) {
-            Ok(RelationDetail {
-                key: self.relation_key.clone(),
-                value,
-            })
-        } else {
-            Err(IncompatibleRelationValue {
+        if !expected_format.is_superset(&received_format) {
+            return Err(InvalidRelationValue::IncompatibleFormat {
                 expected: expected_format.clone(),
                 received: received_format,
-            })
+            });
         }
+
+        if let RelationValue::Number(number) = &value {
+            if !number.is_finite() {
+                return Err(InvalidRelationValue::NonFiniteNumber(*number));
+            }
+        }
+
+        if let (RelationValue::Object(objects), Some(max)) = (&value, self.max_count) {
+            if objects.len() > max as usize {
+                return Err(InvalidRelationValue::TooManyValues {
+                    max,
+                    got: objects.len(),
+                });
+            }
+        }
+
+        if check_well_formed {
+            if let Some(malformed) = value.malformed() {
+                return Err(InvalidRelationValue::MalformedValue {
+                    format: received_format,
+                    value: malformed.to_string(),
+                });
+            }
+        }
+
+        Ok(RelationDetail {
+            key: self.relation_key.clone(),
+            value,
+        })
+    }
+}
+
+/// Error returned when changing a relation to a format anytype-heart can't convert it to
+#[derive(Debug)]
+pub struct FormatChangeUnsupported {
+    pub from: RelationFormat,
+    pub to: RelationFormat,
+}
+
+impl Display for FormatChangeUnsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Changing a relation's format from {} to {} is not supported",
+            self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for FormatChangeUnsupported {}
+
+impl From<FormatChangeUnsupported> for tonic::Status {
+    fn from(value: FormatChangeUnsupported) -> Self {
+        tonic::Status::failed_precondition(format!("{value}"))
+    }
+}
+
+impl RelationFormat {
+    /// Whether values of this format stay valid when the relation changes to the other format
+    fn can_change_to(&self, other: &RelationFormat) -> bool {
+        use RelationFormat::*;
+
+        matches!(
+            (self, other),
+            (Text | Url | Email | Phone, Text | Url | Email | Phone)
+                | (Object { .. }, Object { .. })
+        )
+    }
+}
+
+impl From<Relation> for RelationSpec {
+    fn from(value: Relation) -> Self {
+        value.into_spec()
+    }
+}
+
+impl From<&Relation> for RelationSpec {
+    fn from(value: &Relation) -> Self {
+        value.as_spec()
     }
 }
 
@@ -353,12 +837,26 @@ impl TryFromProst for Relation {
         let format = value.take_enum::<InternalRelationFormat>("relationFormat")?;
         let object_types =
             value.take_optional::<BTreeSet<ObjectTypeId>>("relationFormatObjectTypes")?;
+        let description = value
+            .take_optional::<String>("description")?
+            .filter(|description| !description.is_empty());
+        // anytype-heart uses 0 to represent relations without a limit
+        let max_count = value
+            .take_optional::<f64>("relationMaxCount")?
+            .map(|max_count| max_count as u32)
+            .filter(|max_count| *max_count != 0);
+        let include_time = value
+            .take_optional::<bool>("relationFormatIncludeTime")?
+            .unwrap_or_default();
 
         Ok(Self {
             id,
             name,
             relation_key,
             format: RelationFormat::from_internal(format, object_types),
+            description,
+            max_count,
+            include_time,
         })
     }
 }
@@ -367,6 +865,10 @@ impl crate::space::SearchOutput for Relation {
     const LAYOUT: &'static [crate::pb::models::object_type::Layout] =
         &[crate::pb::models::object_type::Layout::Relation];
     type Id = RelationId;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
 }
 
 /// Internal value only obtainable via [Relation::validate] that guarantees a relation's type has
@@ -381,3 +883,37 @@ impl RelationDetail {
         (self.key.0, self.value.into_prost())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relation_value_reads_whole_numbers_as_i64() {
+        assert_eq!(RelationValue::Number(42.0).as_i64(), Some(42));
+        assert_eq!(RelationValue::Number(-42.0).as_i64(), Some(-42));
+        assert_eq!(
+            RelationValue::Number(i64::MIN as f64).as_i64(),
+            Some(i64::MIN)
+        );
+        assert_eq!(RelationValue::Text("42".to_string()).as_i64(), None);
+    }
+
+    #[test]
+    fn relation_value_rejects_numbers_that_arent_an_i64() {
+        assert_eq!(RelationValue::Number(1.5).as_i64(), None);
+        assert_eq!(RelationValue::Number(-0.25).as_i64(), None);
+        assert_eq!(RelationValue::Number(i64::MAX as f64).as_i64(), None);
+        assert_eq!(RelationValue::Number(-1e19).as_i64(), None);
+        assert_eq!(RelationValue::Number(f64::INFINITY).as_i64(), None);
+        assert_eq!(RelationValue::Number(f64::NAN).as_i64(), None);
+    }
+
+    #[test]
+    fn relation_value_reads_any_number_as_f64() {
+        assert_eq!(RelationValue::Number(1.5).as_f64(), Some(1.5));
+        assert_eq!(RelationValue::Number(1e19).as_f64(), Some(1e19));
+        assert!(RelationValue::Number(f64::NAN).as_f64().unwrap().is_nan());
+        assert_eq!(RelationValue::Checkbox(true).as_f64(), None);
+    }
+}
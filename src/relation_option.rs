@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use crate::{
+    object::ObjectId,
+    prost_ext::{IntoProstValue, ProstConversionError, ProstStruct, TryFromProst},
+    relation::RelationKey,
+};
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelationOptionId(ObjectId);
+
+impl Display for RelationOptionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl IntoProstValue for RelationOptionId {
+    fn into_prost(self) -> prost_types::Value {
+        self.0.into_prost()
+    }
+}
+
+impl TryFromProst for RelationOptionId {
+    type Input = prost_types::value::Kind;
+
+    fn try_from_prost(kind: Self::Input) -> Result<Self, ProstConversionError> {
+        ObjectId::try_from_prost(kind).map(RelationOptionId)
+    }
+}
+
+impl From<RelationOptionId> for ObjectId {
+    fn from(value: RelationOptionId) -> Self {
+        value.0
+    }
+}
+
+/// One of the possible values of a Select or MultiSelect relation
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelationOption {
+    id: RelationOptionId,
+    text: String,
+    color: String,
+    pub(crate) relation_key: RelationKey,
+}
+
+impl RelationOption {
+    pub fn id(&self) -> RelationOptionId {
+        self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The name of the color AnyType apps display this option with, such as `red` or `teal`
+    pub fn color(&self) -> &str {
+        &self.color
+    }
+
+    /// The key of the relation this option belongs to, the same key its values are stored under
+    /// in [Object::raw_details](crate::Object::raw_details)
+    pub fn relation_key(&self) -> &str {
+        &self.relation_key.0
+    }
+}
+
+impl TryFromProst for RelationOption {
+    type Input = prost_types::Struct;
+
+    fn try_from_prost(input: Self::Input) -> Result<Self, ProstConversionError>
+    where
+        Self: Sized,
+    {
+        use crate::pb::models::object_type::Layout;
+
+        let mut value = ProstStruct::from(input);
+
+        let layout = value.take_enum::<Layout>("layout")?;
+        assert!(layout == Layout::RelationOption);
+
+        let id = value.take::<RelationOptionId>("id")?;
+        let text = value.take::<String>("name")?;
+        let color = value
+            .take_optional::<String>("relationOptionColor")?
+            .unwrap_or_default();
+        let relation_key = value.take::<RelationKey>("relationKey")?;
+
+        Ok(Self {
+            id,
+            text,
+            color,
+            relation_key,
+        })
+    }
+}
+
+impl crate::space::SearchOutput for RelationOption {
+    const LAYOUT: &'static [crate::pb::models::object_type::Layout] =
+        &[crate::pb::models::object_type::Layout::RelationOption];
+    type Id = RelationOptionId;
+
+    fn id(&self) -> Self::Id {
+        self.id
+    }
+}
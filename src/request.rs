@@ -1,4 +1,9 @@
-use tonic::{metadata::MetadataMap, IntoRequest, Request};
+use std::future::Future;
+
+use tonic::{metadata::MetadataMap, transport::Channel, IntoRequest, Request};
+
+use crate::client::TokenExpired;
+use crate::pb::client_commands_client::ClientCommandsClient;
 
 pub struct RequestWithToken<'a, T> {
     pub request: T,
@@ -13,3 +18,38 @@ impl<'a, T> IntoRequest<T> for RequestWithToken<'a, T> {
         Request::from_parts(metadata, tonic::Extensions::default(), self.request)
     }
 }
+
+/// Send `request` with the session token through `rpc`, which is given its own handle to the
+/// gRPC client to call the RPC method on
+///
+/// Every request made with the session token goes through here so that anytype-heart rejecting
+/// the token is always reported as [TokenExpired]
+pub(crate) async fn send<T, R, F, Fut>(
+    grpc: &ClientCommandsClient<Channel>,
+    token: &str,
+    request: T,
+    rpc: F,
+) -> Result<R, tonic::Status>
+where
+    F: FnOnce(ClientCommandsClient<Channel>, Request<T>) -> Fut,
+    Fut: Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+{
+    let request = RequestWithToken { request, token }.into_request();
+
+    rpc(grpc.clone(), request)
+        .await
+        .map(tonic::Response::into_inner)
+        .map_err(check_token_error)
+}
+
+/// Map the status of a failed request to [TokenExpired] when anytype-heart rejected the token
+/// itself, leaving any other status as is
+///
+/// Only `Unauthenticated` means that, `PermissionDenied` is also what a read-only space responds
+/// with to a write
+fn check_token_error(status: tonic::Status) -> tonic::Status {
+    match status.code() {
+        tonic::Code::Unauthenticated => TokenExpired.into(),
+        _ => status,
+    }
+}
@@ -0,0 +1,84 @@
+use crate::pb;
+
+/// An error anytype-heart responded with
+///
+/// Every RPC has its own error type with its own set of codes, but they all share the same shape
+/// so they are mapped to [tonic::Status] the same way here. Adding handling for a new code only
+/// needs a change to the invocation of [response_error!] for that RPC
+pub(crate) trait ResponseError {
+    /// Map the error to a [tonic::Status], `None` means the response was successful
+    fn into_status(self) -> Option<tonic::Status>;
+}
+
+/// Turn the error field of an anytype-heart response into a [Result] so it can be handled with `?`
+pub(crate) fn check_response_error<E: ResponseError>(
+    error: Option<E>,
+) -> Result<(), tonic::Status> {
+    match error.and_then(ResponseError::into_status) {
+        Some(status) => Err(status),
+        None => Ok(()),
+    }
+}
+
+/// Implements [ResponseError] for the error of the RPC at the given path inside `pb::rpc`.
+///
+/// Every RPC maps `Null`, `UnknownError` and `BadInput` the same way, codes unique to an RPC can
+/// be mapped to a specific [tonic::Status] constructor in braces after the path. Codes that aren't
+/// mapped, including ones newer than the protos, are treated as internal errors, unless another
+/// constructor is given after `else`
+macro_rules! response_error {
+    ($($segment:ident)::+ $({ $($code:ident => $status:ident),+ $(,)? })? $(else $fallback:ident)?) => {
+        impl ResponseError for pb::rpc::$($segment)::+::response::Error {
+            fn into_status(self) -> Option<tonic::Status> {
+                use pb::rpc::$($segment)::+::response::error::Code;
+
+                #[allow(unused_variables)]
+                let fallback: fn(String) -> tonic::Status = tonic::Status::internal;
+                $(let fallback: fn(String) -> tonic::Status = tonic::Status::$fallback;)?
+
+                // Matching on the raw code instead of `self.code()`, which reads codes newer than
+                // the protos as `Null`, keeps them from passing as success
+                match Code::try_from(self.code) {
+                    Ok(Code::Null) => None,
+                    Ok(Code::UnknownError) => Some(tonic::Status::unknown(self.description)),
+                    Ok(Code::BadInput) => Some(tonic::Status::invalid_argument(self.description)),
+                    $($(Ok(Code::$code) => Some(tonic::Status::$status(self.description)),)+)?
+                    _ => Some(fallback(self.description)),
+                }
+            }
+        }
+    };
+}
+
+response_error!(app::get_version);
+response_error!(app::shutdown);
+response_error!(wallet::create { FailedToCreateLocalRepo => internal });
+response_error!(wallet::recover { FailedToCreateLocalRepo => internal });
+response_error!(wallet::create_session { AppTokenNotFoundInTheCurrentAccount => failed_precondition });
+response_error!(wallet::close_session);
+response_error!(account::create);
+response_error!(account::recover { NeedToRecoverWalletFirst => failed_precondition });
+response_error!(account::select);
+response_error!(account::stop);
+response_error!(metrics::set_parameters);
+response_error!(workspace::open);
+response_error!(object::search);
+response_error!(object::subscribe_ids);
+response_error!(object::search_subscribe);
+response_error!(object::search_unsubscribe);
+response_error!(object::create);
+response_error!(object::create_bookmark);
+response_error!(object::create_relation);
+response_error!(object::create_relation_option);
+response_error!(object::create_object_type);
+response_error!(object::set_details);
+response_error!(object::set_object_type else failed_precondition);
+response_error!(object::set_is_favorite);
+response_error!(object::list_delete);
+response_error!(object::list_export);
+response_error!(object::import);
+response_error!(link_preview);
+response_error!(history::get_versions);
+response_error!(history::show_version);
+response_error!(space::invite_generate else failed_precondition);
+response_error!(space::invite_revoke else failed_precondition);
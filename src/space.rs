@@ -1,22 +1,84 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+use std::future::Future;
 use std::ops::Not;
-use std::sync::Arc;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
-use futures_util::stream::FuturesUnordered;
-use futures_util::TryStreamExt;
+use chrono::{DateTime, Utc};
+use futures_util::future::{select, Either};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
 
-use crate::client::Client;
-use crate::object::{Object, ObjectDescription, ObjectId, ObjectSpec, ObjectUnresolved};
-use crate::object_type::{ObjectType, ObjectTypeSpec, ObjectTypeUnresolved};
+use crate::bookmark::CreatedBookmark;
+use crate::client::{Client, NetworkSync};
+use crate::existence::Existence;
+use crate::export::{temporary_export_dir, ExportFormat};
+use crate::history::{ObjectAtVersion, ObjectVersion};
+use crate::import::{ImportFormat, ImportMode, ImportReport};
+use crate::member::{InviteLink, Permission, SharingRequiresNetworkSync, SpaceMember};
+use crate::object::{
+    unresolved_value, Object, ObjectDescription, ObjectId, ObjectLayout, ObjectSpec,
+    ObjectUnresolved, UnresolvedValue,
+};
+use crate::object_type::{
+    BundledType, ObjectType, ObjectTypeId, ObjectTypeSpec, ObjectTypeUnresolved, RelationGroup,
+};
 use crate::pb::{self, models::block::content::dataview::Filter};
+use crate::plan::CreationPlan;
 use crate::prost_ext::{IntoProstValue, ProstStruct, TryFromProst};
-use crate::relation::{Relation, RelationDetail, RelationSpec};
-use crate::request::RequestWithToken;
+use crate::raw_value::RawValue;
+use crate::relation::{
+    Relation, RelationDescription, RelationFormat, RelationId, RelationSpec, RelationValue,
+};
+use crate::relation_option::RelationOption;
+use crate::response::check_response_error;
+use crate::space_info::SpaceInfo;
+use crate::subscription::{ObjectEvent, ObjectSubscription, SubscriptionStream};
 
 #[derive(Debug)]
 pub(crate) struct SpaceInner {
     pub(crate) client: Client,
-    pub(crate) info: pb::models::account::Info,
+    pub(crate) space_id: String,
+    /// Kept up to date by [Space::refresh]
+    info: RwLock<pb::models::account::Info>,
+    /// The identity of the account this space was opened with
+    identity: String,
+    /// Filter for only objects in this space, it's part of every search so it's built once
+    space_filter: Filter,
+    /// Whether this space was opened with [AuthorizedAnytypeClient::open_space_readonly]
+    ///
+    /// [AuthorizedAnytypeClient::open_space_readonly]: crate::AuthorizedAnytypeClient::open_space_readonly
+    pub(crate) read_only: bool,
+}
+
+impl SpaceInner {
+    pub(crate) fn new(client: Client, info: pb::models::account::Info, identity: String) -> Self {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let space_filter = Filter {
+            operator: Operator::And.into(),
+            relation_key: "spaceId".to_string(),
+            condition: Condition::In.into(),
+            value: Some(vec![info.account_space_id.clone().into_prost()].into_prost()),
+
+            ..Default::default()
+        };
+
+        Self {
+            client,
+            space_id: info.account_space_id.clone(),
+            info: RwLock::new(info),
+            identity,
+            space_filter,
+            read_only: false,
+        }
+    }
+
+    pub(crate) fn info(&self) -> RwLockReadGuard<'_, pb::models::account::Info> {
+        self.info.read().expect("space info lock was poisoned")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -24,43 +86,293 @@ pub struct Space {
     pub(crate) inner: Arc<SpaceInner>,
 }
 
+/// Error returned when searching by an empty name, which anytype-heart would treat as matching
+/// everything
+///
+/// It reaches callers as a [tonic::Status], use [EmptyName::from_status] to get it back out of one
+#[derive(Debug)]
+pub struct EmptyName;
+
+impl EmptyName {
+    /// The error `status` was created from, if it was created from one
+    pub fn from_status(status: &tonic::Status) -> Option<&EmptyName> {
+        std::error::Error::source(status)?.downcast_ref::<EmptyName>()
+    }
+}
+
+impl Display for EmptyName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Can't search by an empty name")
+    }
+}
+
+impl std::error::Error for EmptyName {}
+
+impl From<EmptyName> for tonic::Status {
+    fn from(value: EmptyName) -> Self {
+        let mut status = tonic::Status::invalid_argument(format!("{value}"));
+        status.set_source(Arc::new(value));
+        status
+    }
+}
+
+/// Error returned when trying to change anything in a space opened with
+/// [AuthorizedAnytypeClient::open_space_readonly]
+///
+/// [AuthorizedAnytypeClient::open_space_readonly]: crate::AuthorizedAnytypeClient::open_space_readonly
+#[derive(Debug)]
+pub struct ReadOnly;
+
+impl Display for ReadOnly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Can't make changes to a space opened read-only")
+    }
+}
+
+impl std::error::Error for ReadOnly {}
+
+impl From<ReadOnly> for tonic::Status {
+    fn from(value: ReadOnly) -> Self {
+        tonic::Status::permission_denied(format!("{value}"))
+    }
+}
+
+/// Error returned by [Space::get_object_type] and [Space::obtain_object_type] when an object type
+/// of the requested name exists with other recommended relations
+///
+/// It reaches callers as a [tonic::Status], use [RecommendedRelationsMismatch::from_status] to
+/// get it back out of one
+#[derive(Debug)]
+pub struct RecommendedRelationsMismatch {
+    /// The name of the object type
+    pub object_type: String,
+    /// The recommended relations that were asked for
+    pub requested: BTreeSet<RelationSpec>,
+    /// The recommended relations the existing object type has
+    pub received: BTreeSet<RelationSpec>,
+}
+
+impl RecommendedRelationsMismatch {
+    /// The mismatch `status` was created from, if it was created from one
+    pub fn from_status(status: &tonic::Status) -> Option<&RecommendedRelationsMismatch> {
+        std::error::Error::source(status)?.downcast_ref::<RecommendedRelationsMismatch>()
+    }
+}
+
+impl Display for RecommendedRelationsMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_specs(
+            f: &mut std::fmt::Formatter<'_>,
+            specs: &BTreeSet<RelationSpec>,
+        ) -> std::fmt::Result {
+            if specs.is_empty() {
+                return f.write_str(" none");
+            }
+            for spec in specs {
+                write!(f, "\n  - {} ({})", spec.name, spec.format)?;
+            }
+            Ok(())
+        }
+
+        write!(
+            f,
+            "ObjectType `{}` exists but has different recommended relations from requested recommended relations\nRequested recommended relations:",
+            self.object_type
+        )?;
+        write_specs(f, &self.requested)?;
+        f.write_str("\nReceived recommended relations:")?;
+        write_specs(f, &self.received)
+    }
+}
+
+impl std::error::Error for RecommendedRelationsMismatch {}
+
+impl From<RecommendedRelationsMismatch> for tonic::Status {
+    fn from(value: RecommendedRelationsMismatch) -> Self {
+        let mut status = tonic::Status::failed_precondition(format!("{value}"));
+        status.set_source(Arc::new(value));
+        status
+    }
+}
+
+fn ensure_name_not_empty(name: &str) -> Result<(), EmptyName> {
+    if name.trim().is_empty() {
+        return Err(EmptyName);
+    }
+
+    Ok(())
+}
+
+/// How many ids [Space::get_objects] searches for in a single request
+const GET_OBJECTS_BATCH_SIZE: usize = 200;
+
+/// The `uniqueKey` of the type every template is an object of, whatever type it's a template for
+const TEMPLATE_TYPE_UNIQUE_KEY: &str = "ot-template";
+
+static NEXT_SUBSCRIPTION: AtomicU64 = AtomicU64::new(0);
+
+/// A live anytype-heart subscription
+///
+/// With the `background-tasks` feature it's cancelled in the background when dropped, without it
+/// it has to be cancelled with [Subscription::cancel]
+pub(crate) struct Subscription {
+    client: Client,
+    sub_id: String,
+}
+
+impl Subscription {
+    pub(crate) fn sub_id(&self) -> &str {
+        &self.sub_id
+    }
+
+    /// Cancel the subscription and wait for anytype-heart to stop it
+    pub(crate) async fn cancel(mut self) -> Result<(), tonic::Status> {
+        // Taking the id leaves nothing for drop to cancel again
+        let sub_id = std::mem::take(&mut self.sub_id);
+
+        Self::unsubscribe(self.client.clone(), sub_id).await
+    }
+
+    async fn unsubscribe(client: Client, sub_id: String) -> Result<(), tonic::Status> {
+        let response = client
+            .request(
+                pb::rpc::object::search_unsubscribe::Request {
+                    sub_ids: vec![sub_id],
+                },
+                |mut grpc, request| async move { grpc.object_search_unsubscribe(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)
+    }
+}
+
+fn next_sub_id() -> String {
+    format!(
+        "anytype-friend-{}",
+        NEXT_SUBSCRIPTION.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Cancelling on drop needs a background task, without one subscriptions can only be cancelled
+/// with [Subscription::cancel]
+#[cfg(feature = "background-tasks")]
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let sub_id = std::mem::take(&mut self.sub_id);
+        if sub_id.is_empty() {
+            return;
+        }
+
+        let client = self.client.clone();
+        tokio::spawn(async move {
+            if let Err(error) = Subscription::unsubscribe(client, sub_id.clone()).await {
+                tracing::warn!(%sub_id, %error, "failed to cancel subscription");
+            }
+        });
+    }
+}
+
 /// Internal trait representing a known AnyType object layout.
 ///
 /// This trait should only be implemented for types that should never fail their
 /// TryFrom conversion
 pub(crate) trait SearchOutput: TryFromProst<Input = prost_types::Struct> {
     const LAYOUT: &'static [pb::models::object_type::Layout];
-    type Id: Into<ObjectId>;
+    type Id;
+
+    fn id(&self) -> Self::Id;
+}
+
+fn record_id(record: &prost_types::Struct) -> &str {
+    match record
+        .fields
+        .get("id")
+        .and_then(|value| value.kind.as_ref())
+    {
+        Some(prost_types::value::Kind::StringValue(id)) => id,
+        _ => "<missing id>",
+    }
 }
 
 impl Space {
-    async fn search_objects<O>(&self, mut filters: Vec<Filter>) -> Result<Vec<O>, tonic::Status>
+    pub fn info(&self) -> SpaceInfo {
+        SpaceInfo(self.inner.info().clone())
+    }
+
+    /// Re-read this space's info from anytype-heart, which is otherwise only read once when the
+    /// space is opened and goes stale when it changes elsewhere
+    pub async fn refresh(&self) -> Result<(), tonic::Status> {
+        let Some(info) = self
+            .inner
+            .client
+            .workspace_open(&self.inner.space_id)
+            .await?
+        else {
+            return Err(tonic::Status::not_found(format!(
+                "Space {} no longer exists",
+                self.inner.space_id
+            )));
+        };
+
+        *self
+            .inner
+            .info
+            .write()
+            .expect("space info lock was poisoned") = info;
+
+        Ok(())
+    }
+
+    /// Whether this space was opened read-only, in which case every change to it or its objects
+    /// fails with [ReadOnly]
+    pub fn is_read_only(&self) -> bool {
+        self.inner.read_only
+    }
+
+    fn ensure_writable(&self) -> Result<(), ReadOnly> {
+        if self.inner.read_only {
+            return Err(ReadOnly);
+        }
+
+        Ok(())
+    }
+
+    /// Search for records of the given output type, restricted to only the given detail keys
+    /// unless `keys` is empty
+    async fn search_records<O>(
+        &self,
+        filters: Vec<Filter>,
+        keys: Vec<String>,
+    ) -> Result<Vec<prost_types::Struct>, tonic::Status>
     where
         O: SearchOutput,
     {
+        self.search_records_in_layouts(
+            filters,
+            keys,
+            O::LAYOUT.iter().map(|layout| i32::from(*layout)),
+        )
+        .await
+    }
+
+    /// Add the filters every search needs to the given ones, for only objects in this space with
+    /// one of the given layouts
+    fn scope_filters(&self, filters: &mut Vec<Filter>, layouts: impl IntoIterator<Item = i32>) {
         use pb::models::block::content::dataview::filter::{Condition, Operator};
 
         filters.extend([
             // Always filter for only objects in this space
-            Filter {
-                operator: Operator::And.into(),
-                relation_key: "spaceId".to_string(),
-                condition: Condition::In.into(),
-                value: Some(
-                    vec![self.inner.info.account_space_id.clone().into_prost()].into_prost(),
-                ),
-
-                ..Default::default()
-            },
+            self.inner.space_filter.clone(),
             // Always filter for only objects that match the desired output type
             Filter {
                 operator: Operator::And.into(),
                 relation_key: "layout".to_string(),
                 condition: Condition::In.into(),
                 value: Some(
-                    O::LAYOUT
-                        .iter()
-                        .map(|layout| (i32::from(*layout) as f64).into_prost())
+                    layouts
+                        .into_iter()
+                        .map(|layout| (layout as f64).into_prost())
                         .collect::<Vec<_>>()
                         .into_prost(),
                 ),
@@ -68,33 +380,20 @@ impl Space {
                 ..Default::default()
             },
         ]);
+    }
 
-        let response = self
-            .inner
-            .client
-            .grpc
-            .clone()
-            .object_search(RequestWithToken {
-                request: pb::rpc::object::search::Request {
-                    filters,
-                    ..Default::default()
-                },
-                token: &self.inner.client.token,
-            })
-            .await?
-            .into_inner();
-
-        if let Some(error) = response.error {
-            use pb::rpc::object::search::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
-        }
+    /// Same as [Space::search_records] but for records of any of the given layouts
+    async fn search_records_in_layouts(
+        &self,
+        mut filters: Vec<Filter>,
+        keys: Vec<String>,
+        layouts: impl IntoIterator<Item = i32>,
+    ) -> Result<Vec<prost_types::Struct>, tonic::Status> {
+        self.scope_filters(&mut filters, layouts);
 
-        Ok(response
-            .records
+        Ok(self
+            .run_search(filters, keys)
+            .await?
             .into_iter()
             .map(ProstStruct::from)
             // We always filter outputs that are hidden so that they aren't used
@@ -107,41 +406,260 @@ impl Space {
                     .not()
                     .then_some(fields.into_inner())
             })
-            .map(O::try_from_prost)
-            // TODO: We are guranteed via the trait SearchOutput that this
-            // shouldn't need to filter anything, if it were to filter something
-            // we should still warn though as that would imply bugs in the
-            // internal code
-            .filter_map(Result::ok)
             .collect::<Vec<_>>())
     }
 
+    /// Search for records of every layout in this space, hidden ones included, for maintenance
+    /// that has to reach every object such as [Space::deduplicate_relation]
+    async fn search_all_records(
+        &self,
+        mut filters: Vec<Filter>,
+        keys: Vec<String>,
+    ) -> Result<Vec<prost_types::Struct>, tonic::Status> {
+        filters.push(self.inner.space_filter.clone());
+
+        self.run_search(filters, keys).await
+    }
+
+    async fn run_search(
+        &self,
+        filters: Vec<Filter>,
+        keys: Vec<String>,
+    ) -> Result<Vec<prost_types::Struct>, tonic::Status> {
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::search::Request {
+                    filters,
+                    keys,
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_search(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(response.records)
+    }
+
+    async fn search_objects<O>(&self, filters: Vec<Filter>) -> Result<Vec<O>, tonic::Status>
+    where
+        O: SearchOutput,
+    {
+        Ok(self
+            .search_records::<O>(filters, Vec::new())
+            .await?
+            .into_iter()
+            // We are guranteed via the trait SearchOutput that this shouldn't need to filter
+            // anything, if it does filter something that implies a bug in the internal code or
+            // an unexpected shape coming from anytype-heart
+            .filter_map(|record| {
+                let id = record_id(&record).to_string();
+
+                match O::try_from_prost(record) {
+                    Ok(output) => Some(output),
+                    Err(error) => {
+                        tracing::warn!(%id, %error, "dropping search record that failed conversion");
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>())
+    }
+
+    /// Same as [Space::search_objects] but fails with the first conversion error instead of
+    /// skipping records that failed to convert
+    async fn search_objects_strict<O>(&self, filters: Vec<Filter>) -> Result<Vec<O>, tonic::Status>
+    where
+        O: SearchOutput,
+    {
+        self.search_records::<O>(filters, Vec::new())
+            .await?
+            .into_iter()
+            .map(|record| {
+                let id = record_id(&record).to_string();
+
+                O::try_from_prost(record).map_err(|error| {
+                    tonic::Status::internal(format!("Failed to convert record {id}: {error}"))
+                })
+            })
+            .collect()
+    }
+
+    /// Get the objects with the given ids in the same order as the ids
+    ///
+    /// The ids are searched for in concurrent batches of [GET_OBJECTS_BATCH_SIZE] so that large
+    /// Object relations don't turn into a single giant request
+    ///
+    /// Ids that don't resolve to an object are omitted, as are repeated ids after their first
+    /// occurrence
     pub(crate) async fn get_objects<O>(
         &self,
         ids: impl IntoIterator<Item = O::Id>,
     ) -> Result<Vec<O>, tonic::Status>
     where
         O: SearchOutput,
+        O::Id: Into<ObjectId>,
     {
         use pb::models::block::content::dataview::filter::{Condition, Operator};
 
-        let objects = self
-            .search_objects::<O>(vec![Filter {
+        let ids = ids.into_iter().map(Into::into).collect::<Vec<ObjectId>>();
+
+        let mut objects = stream::iter(ids.chunks(GET_OBJECTS_BATCH_SIZE).map(|batch| {
+            self.search_objects::<O>(vec![Filter {
                 operator: Operator::And.into(),
                 relation_key: "id".to_string(),
                 condition: Condition::In.into(),
                 value: Some(
-                    ids.into_iter()
-                        .map(|id| id.into().into_prost())
+                    batch
+                        .iter()
+                        .map(|id| id.into_prost())
                         .collect::<Vec<_>>()
                         .into_prost(),
                 ),
 
                 ..Default::default()
             }])
-            .await?;
+        }))
+        .buffer_unordered(self.inner.client.max_concurrent_requests)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .map(|object| (object.id().into(), object))
+        .collect::<HashMap<ObjectId, O>>();
+
+        Ok(ids.iter().filter_map(|id| objects.remove(id)).collect())
+    }
+
+    /// Get the objects with the given ids in the same order as the ids
+    ///
+    /// Ids that don't resolve to an object in this space are silently omitted, as are repeated
+    /// ids after their first occurrence
+    pub async fn get_objects_by_id(
+        &self,
+        ids: impl IntoIterator<Item = ObjectId>,
+    ) -> Result<Vec<Object>, tonic::Status> {
+        Ok(self
+            .get_objects::<ObjectUnresolved>(ids)
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// Same as [Space::get_objects_by_id] for a single object but for objects of any of the
+    /// given layouts, like [Space::list_objects_with_layouts]
+    pub(crate) async fn get_object_with_layouts(
+        &self,
+        id: ObjectId,
+        layouts: &[ObjectLayout],
+    ) -> Result<Option<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let Some(record) = self
+            .search_records_in_layouts(
+                vec![Filter {
+                    operator: Operator::And.into(),
+                    relation_key: "id".to_string(),
+                    condition: Condition::Equal.into(),
+                    value: Some(id.into_prost()),
+
+                    ..Default::default()
+                }],
+                Vec::new(),
+                layouts.iter().map(|layout| layout.into_raw()),
+            )
+            .await?
+            .pop()
+        else {
+            return Ok(None);
+        };
 
-        Ok(objects)
+        ObjectUnresolved::try_from_any_layout(record)
+            .map(|object| Some(object.resolve(self.clone())))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    /// Get the values of the given relations on each of the given objects, in the same order as
+    /// the objects
+    ///
+    /// Like [Object::get] but the objects referenced by Object relations across every value are
+    /// fetched together instead of once per value, which matters when reading a table of objects.
+    /// Relations without a value on an object are left out of its map. Select, MultiSelect and
+    /// FileOrMedia relations can't be read yet and fail with [tonic::Code::Unimplemented]
+    pub async fn get_values(
+        &self,
+        objects: &[Object],
+        relations: &[Relation],
+    ) -> Result<Vec<HashMap<Relation, RelationValue>>, tonic::Status> {
+        if let Some(relation) = relations.iter().find(|relation| {
+            matches!(
+                relation.format(),
+                RelationFormat::Select | RelationFormat::MultiSelect | RelationFormat::FileOrMedia
+            )
+        }) {
+            return Err(tonic::Status::unimplemented(format!(
+                "Reading values of {} relations like `{}` isn't supported yet",
+                relation.format(),
+                relation.name()
+            )));
+        }
+
+        let values = objects
+            .iter()
+            .map(|object| {
+                relations
+                    .iter()
+                    .filter_map(|relation| {
+                        let kind = object.raw_kind(relation)?;
+                        unresolved_value(relation, kind).map(|value| (relation, value))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let ids = values
+            .iter()
+            .flatten()
+            .filter_map(|(_, value)| match value {
+                UnresolvedValue::Objects(ids) => Some(ids.iter().copied()),
+                UnresolvedValue::Value(_) => None,
+            })
+            .flatten()
+            .collect::<BTreeSet<_>>();
+        let referenced = self
+            .get_objects::<ObjectUnresolved>(ids)
+            .await?
+            .into_iter()
+            .map(|object| {
+                let object = object.resolve(self.clone());
+                (object.id(), object)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Ok(values
+            .into_iter()
+            .map(|values| {
+                values
+                    .into_iter()
+                    .map(|(relation, value)| {
+                        let value = match value {
+                            UnresolvedValue::Value(value) => value,
+                            // Like get_objects, ids that don't resolve to an object are omitted
+                            UnresolvedValue::Objects(ids) => RelationValue::Object(
+                                ids.iter()
+                                    .filter_map(|id| referenced.get(id).cloned())
+                                    .collect(),
+                            ),
+                        };
+                        (relation.clone(), value)
+                    })
+                    .collect()
+            })
+            .collect())
     }
 
     pub async fn get_relation(
@@ -150,6 +668,8 @@ impl Space {
     ) -> Result<Option<Relation>, tonic::Status> {
         use pb::models::block::content::dataview::filter::{Condition, Operator};
 
+        ensure_name_not_empty(&relation_spec.name)?;
+
         let mut relations = self
             .search_objects::<Relation>(vec![Filter {
                 operator: Operator::And.into(),
@@ -184,33 +704,50 @@ impl Space {
         }
     }
 
+    /// Get one of the relations bundled with anytype, like `description`, `tag` or `source`, by
+    /// its stable relation key instead of its possibly localized name
+    pub async fn bundled_relation(&self, key: &str) -> Result<Option<Relation>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let mut relations = self
+            .search_objects::<Relation>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "relationKey".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(key.to_string().into_prost()),
+
+                ..Default::default()
+            }])
+            .await?;
+
+        match relations.len() {
+            0 => Ok(None),
+            1 => Ok(Some(relations.swap_remove(0))),
+            _ => Err(tonic::Status::failed_precondition(format!(
+                "More than one relation with same key {key}"
+            ))),
+        }
+    }
+
     pub async fn create_relation(
         &self,
-        relation_spec: &RelationSpec,
+        relation: RelationDescription,
     ) -> Result<Relation, tonic::Status> {
+        self.ensure_writable()?;
+
         let response = self
             .inner
             .client
-            .grpc
-            .clone()
-            .object_create_relation(RequestWithToken {
-                request: pb::rpc::object::create_relation::Request {
-                    space_id: self.inner.info.account_space_id.clone(),
-                    details: Some(relation_spec.clone().into()),
+            .request(
+                pb::rpc::object::create_relation::Request {
+                    space_id: self.inner.space_id.clone(),
+                    details: Some(relation.into()),
                 },
-                token: &self.inner.client.token,
-            })
-            .await?
-            .into_inner();
-
-        if let Some(error) = response.error {
-            use pb::rpc::object::create_relation::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
-        }
+                |mut grpc, request| async move { grpc.object_create_relation(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
 
         let Some(details) = response.details else {
             return Err(tonic::Status::internal(
@@ -222,32 +759,272 @@ impl Space {
             .map_err(|error| tonic::Status::internal(format!("{error}")))
     }
 
-    pub async fn obtain_relation(
+    /// Get every option of a Select or MultiSelect relation
+    pub async fn get_relation_options(
         &self,
-        relation_spec: &RelationSpec,
-    ) -> Result<Relation, tonic::Status> {
-        match self.get_relation(relation_spec).await? {
-            None => self.create_relation(relation_spec).await,
-            Some(relation) => Ok(relation),
-        }
+        relation: &Relation,
+    ) -> Result<Vec<RelationOption>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        self.search_objects::<RelationOption>(vec![Filter {
+            operator: Operator::And.into(),
+            relation_key: "relationKey".to_string(),
+            condition: Condition::Equal.into(),
+            value: Some(relation.relation_key.0.clone().into_prost()),
+
+            ..Default::default()
+        }])
+        .await
     }
 
-    pub async fn get_object_type(
+    /// Add an option to a Select or MultiSelect relation, `color` is the name of one of the colors
+    /// AnyType apps offer such as `red` or `teal`
+    pub async fn create_relation_option(
         &self,
-        object_type_spec: &ObjectTypeSpec,
-    ) -> Result<Option<ObjectType>, tonic::Status> {
-        use pb::models::block::content::dataview::filter::{Condition, Operator};
+        relation: &Relation,
+        text: &str,
+        color: &str,
+    ) -> Result<RelationOption, tonic::Status> {
+        self.ensure_writable()?;
 
-        let mut object_types = self
-            .search_objects::<ObjectTypeUnresolved>(vec![Filter {
-                operator: Operator::And.into(),
-                relation_key: "name".to_string(),
-                condition: Condition::Like.into(),
-                value: Some(object_type_spec.name.clone().into_prost()),
+        let details = prost_types::Struct {
+            fields: BTreeMap::from([
+                (
+                    "relationKey".to_string(),
+                    relation.relation_key.0.clone().into_prost(),
+                ),
+                ("name".to_string(), text.to_string().into_prost()),
+                (
+                    "relationOptionColor".to_string(),
+                    color.to_string().into_prost(),
+                ),
+            ]),
+        };
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::create_relation_option::Request {
+                    space_id: self.inner.space_id.clone(),
+                    details: Some(details),
+                },
+                |mut grpc, request| async move {
+                    grpc.object_create_relation_option(request).await
+                },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let Some(details) = response.details else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with a relation option's details",
+            ));
+        };
+
+        RelationOption::try_from_prost(details)
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    pub async fn obtain_relation(
+        &self,
+        relation_spec: &RelationSpec,
+    ) -> Result<Relation, tonic::Status> {
+        self.obtain_relation_with_existence(relation_spec)
+            .await
+            .map(|(relation, _)| relation)
+    }
+
+    /// Like [Space::obtain_relation] but also reports whether the relation was created or found
+    pub async fn obtain_relation_with_existence(
+        &self,
+        relation_spec: &RelationSpec,
+    ) -> Result<(Relation, Existence), tonic::Status> {
+        match self.get_relation(relation_spec).await? {
+            None => self
+                .create_relation(relation_spec.as_description())
+                .await
+                .map(|relation| (relation, Existence::Created)),
+            Some(relation) => Ok((relation, Existence::Found)),
+        }
+    }
+
+    /// Merge relations sharing the name `name` into one, for spaces where racing
+    /// [Space::obtain_relation] calls created more than one and [Space::get_relation] now fails
+    ///
+    /// The relation with the lowest id is kept, so that cleanups racing each other agree on it.
+    /// Object types referencing a duplicate in any of their [relation
+    /// groups](ObjectType::relation_groups) reference the kept relation instead, values of a
+    /// duplicate on objects of any layout are moved over to the kept relation unless an object
+    /// already has a value for it, and the duplicates are deleted last. Relations of different
+    /// formats can't be merged and fail with [tonic::Code::FailedPrecondition]
+    ///
+    /// A failure partway through leaves the space half merged, but every step is safe to repeat
+    /// and the duplicates are only deleted once everything else is done, so calling this again
+    /// finishes the merge
+    pub async fn deduplicate_relation(&self, name: &str) -> Result<Relation, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        ensure_name_not_empty(name)?;
+        self.ensure_writable()?;
+
+        let mut relations = self
+            .search_objects::<Relation>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "name".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(name.to_string().into_prost()),
 
                 ..Default::default()
             }])
             .await?;
+        relations.sort_by_key(|relation| relation.id());
+
+        let mut relations = relations.into_iter();
+        let Some(canonical) = relations.next() else {
+            return Err(tonic::Status::not_found(format!(
+                "No relation with name {name}"
+            )));
+        };
+        let duplicates = relations.collect::<Vec<_>>();
+
+        if let Some(duplicate) = duplicates
+            .iter()
+            .find(|duplicate| duplicate.format() != canonical.format())
+        {
+            return Err(tonic::Status::failed_precondition(format!(
+                "Relations named {name} have different formats {} and {}, they can't be merged",
+                canonical.format(),
+                duplicate.format()
+            )));
+        }
+
+        for duplicate in &duplicates {
+            for group in RelationGroup::ALL {
+                let key = group.detail_key();
+                let object_types = self
+                    .search_records::<ObjectTypeUnresolved>(
+                        vec![Filter {
+                            operator: Operator::And.into(),
+                            relation_key: key.to_string(),
+                            condition: Condition::In.into(),
+                            value: Some(vec![duplicate.id().into_prost()].into_prost()),
+
+                            ..Default::default()
+                        }],
+                        vec!["id".to_string(), key.to_string()],
+                    )
+                    .await?;
+
+                for object_type in object_types {
+                    let mut object_type = ProstStruct::from(object_type);
+                    let id = object_type
+                        .take::<ObjectId>("id")
+                        .map_err(|error| tonic::Status::internal(format!("{error}")))?;
+                    let relations = object_type
+                        .take_optional::<Vec<RelationId>>(key)
+                        .map_err(|error| tonic::Status::internal(format!("{error}")))?
+                        .unwrap_or_default();
+
+                    // The kept relation takes the first duplicate's place in the group's order
+                    let mut seen = HashSet::new();
+                    let relations = relations
+                        .into_iter()
+                        .map(|id| {
+                            if id == duplicate.id() {
+                                canonical.id()
+                            } else {
+                                id
+                            }
+                        })
+                        .filter(|id| seen.insert(*id))
+                        .map(IntoProstValue::into_prost)
+                        .collect::<Vec<_>>();
+
+                    self.set_detail(id, key.to_string(), relations.into_prost())
+                        .await?;
+                }
+            }
+
+            let objects = self
+                .search_all_records(
+                    vec![Filter {
+                        operator: Operator::And.into(),
+                        relation_key: duplicate.relation_key.0.clone(),
+                        condition: Condition::NotEmpty.into(),
+
+                        ..Default::default()
+                    }],
+                    vec![
+                        "id".to_string(),
+                        canonical.relation_key.0.clone(),
+                        duplicate.relation_key.0.clone(),
+                    ],
+                )
+                .await?;
+
+            for mut object in objects {
+                let Some(id) = object
+                    .fields
+                    .get("id")
+                    .and_then(|value| value.kind.clone())
+                    .and_then(|kind| ObjectId::try_from_prost(kind).ok())
+                else {
+                    continue;
+                };
+
+                let has_canonical_value = !matches!(
+                    object
+                        .fields
+                        .get(&canonical.relation_key.0)
+                        .and_then(|value| value.kind.as_ref()),
+                    None | Some(prost_types::value::Kind::NullValue(_))
+                );
+                if let (false, Some(value)) = (
+                    has_canonical_value,
+                    object.fields.remove(&duplicate.relation_key.0),
+                ) {
+                    self.set_detail(id, canonical.relation_key.0.clone(), value)
+                        .await?;
+                }
+                self.clear_relation(id, duplicate).await?;
+            }
+        }
+
+        if !duplicates.is_empty() {
+            self.delete_objects(duplicates.iter().map(|duplicate| duplicate.id().into()))
+                .await?;
+        }
+
+        Ok(canonical)
+    }
+
+    /// Compute whether [Space::obtain_relation] would create the relation without creating
+    /// anything
+    pub async fn plan_obtain_relation(
+        &self,
+        relation_spec: &RelationSpec,
+    ) -> Result<CreationPlan, tonic::Status> {
+        let mut plan = CreationPlan::default();
+
+        if self.get_relation(relation_spec).await?.is_none() {
+            plan.relations_to_create.push(relation_spec.clone());
+        }
+
+        Ok(plan)
+    }
+
+    /// Get the object type with the spec's name, compared regardless of case, failing if its
+    /// recommended relations differ from the spec's
+    ///
+    /// The spec's icon and color are ignored, they only matter when the type gets created
+    pub async fn get_object_type(
+        &self,
+        object_type_spec: &ObjectTypeSpec,
+    ) -> Result<Option<ObjectType>, tonic::Status> {
+        let mut object_types = self
+            .search_object_types_named(&object_type_spec.name)
+            .await?;
 
         match object_types.len() {
             0 => Ok(None),
@@ -264,15 +1041,12 @@ impl Space {
                 if relations_specs == object_type_spec.recommended_relations {
                     Ok(Some(output))
                 } else {
-                    Err(tonic::Status::failed_precondition(format!(
-                        "ObjectType `{}` exists but has different recommended relations from requested recommended relations:
-Requested recommended relations: {:?}
-
-Received recommended relations: {:?}",
-                        object_type_spec.name,
-                        relations_specs,
-                        object_type_spec.recommended_relations
-                    )))
+                    Err(RecommendedRelationsMismatch {
+                        object_type: output.name().to_string(),
+                        requested: object_type_spec.recommended_relations.clone(),
+                        received: relations_specs,
+                    }
+                    .into())
                 }
             }
             _ => Err(tonic::Status::failed_precondition(format!(
@@ -282,17 +1056,95 @@ Received recommended relations: {:?}",
         }
     }
 
+    /// Get every object type named `name`, compared regardless of case
+    ///
+    /// Unlike [Space::get_object_type] this doesn't fail when several object types share the same
+    /// name, which can happen after an import, leaving it to the caller to disambiguate between
+    /// them by their recommended relations or id
+    pub async fn get_object_types_by_name(
+        &self,
+        name: &str,
+    ) -> Result<Vec<ObjectType>, tonic::Status> {
+        let object_types = self.search_object_types_named(name).await?;
+
+        let mut output = Vec::with_capacity(object_types.len());
+        for object_type in object_types {
+            output.push(object_type.slow_resolve(self.clone()).await?);
+        }
+
+        Ok(output)
+    }
+
+    /// Search for the object types named `name`, compared regardless of case
+    ///
+    /// Names are matched regardless of case so that "bookmark" finds the bundled Bookmark type
+    /// instead of creating another one. Like narrows the search down case-insensitively but also
+    /// matches names that merely contain the requested one, so those are filtered out after
+    async fn search_object_types_named(
+        &self,
+        name: &str,
+    ) -> Result<Vec<ObjectTypeUnresolved>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        ensure_name_not_empty(name)?;
+
+        let mut object_types = self
+            .search_objects::<ObjectTypeUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "name".to_string(),
+                condition: Condition::Like.into(),
+                value: Some(name.to_string().into_prost()),
+
+                ..Default::default()
+            }])
+            .await?;
+        let name = name.to_lowercase();
+        object_types.retain(|object_type| object_type.name.to_lowercase() == name);
+
+        Ok(object_types)
+    }
+
+    /// Get one of the object types bundled with every space by its stable unique key instead of
+    /// its display name
+    pub async fn bundled_type(&self, key: BundledType) -> Result<ObjectType, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let mut object_types = self
+            .search_objects::<ObjectTypeUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "uniqueKey".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(key.unique_key().to_string().into_prost()),
+
+                ..Default::default()
+            }])
+            .await?;
+
+        if object_types.is_empty() {
+            return Err(tonic::Status::not_found(format!(
+                "Bundled object type `{}` doesn't exist in this space",
+                key.unique_key()
+            )));
+        }
+
+        object_types.swap_remove(0).slow_resolve(self.clone()).await
+    }
+
     pub async fn create_object_type(
         &self,
         object_type_spec: &ObjectTypeSpec,
     ) -> Result<ObjectType, tonic::Status> {
-        let recommended_relations = object_type_spec
-            .recommended_relations
-            .iter()
-            .map(|relation_spec| async { self.obtain_relation(relation_spec).await })
-            .collect::<FuturesUnordered<_>>()
-            .try_collect::<BTreeSet<_>>()
-            .await?;
+        self.ensure_writable()?;
+
+        let recommended_relations = stream::iter(
+            object_type_spec
+                .recommended_relations
+                .iter()
+                .map(|relation_spec| async { self.obtain_relation(relation_spec).await }),
+        )
+        .buffer_unordered(self.inner.client.max_concurrent_requests)
+        .try_collect::<BTreeSet<_>>()
+        .await?;
         let relation_ids = recommended_relations
             .clone()
             .into_iter()
@@ -302,28 +1154,18 @@ Received recommended relations: {:?}",
         let response = self
             .inner
             .client
-            .grpc
-            .clone()
-            .object_create_object_type(RequestWithToken {
-                request: pb::rpc::object::create_object_type::Request {
-                    space_id: self.inner.info.account_space_id.clone(),
+            .request(
+                pb::rpc::object::create_object_type::Request {
+                    space_id: self.inner.space_id.clone(),
                     details: Some(object_type_spec.to_struct(relation_ids)),
 
                     ..Default::default()
                 },
-                token: &self.inner.client.token,
-            })
-            .await?
-            .into_inner();
-
-        if let Some(error) = response.error {
-            use pb::rpc::object::create_object_type::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
-        }
+                |mut grpc, request| async move { grpc.object_create_object_type(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
 
         let Some(details) = response.details else {
             return Err(tonic::Status::internal(
@@ -340,38 +1182,86 @@ Received recommended relations: {:?}",
         &self,
         object_type_spec: &ObjectTypeSpec,
     ) -> Result<ObjectType, tonic::Status> {
+        self.obtain_object_type_with_existence(object_type_spec)
+            .await
+            .map(|(object_type, _)| object_type)
+    }
+
+    /// Like [Space::obtain_object_type] but also reports whether the object type was created or
+    /// found
+    pub async fn obtain_object_type_with_existence(
+        &self,
+        object_type_spec: &ObjectTypeSpec,
+    ) -> Result<(ObjectType, Existence), tonic::Status> {
         match self.get_object_type(object_type_spec).await? {
-            None => self.create_object_type(object_type_spec).await,
-            Some(object_type) => Ok(object_type),
+            None => self
+                .create_object_type(object_type_spec)
+                .await
+                .map(|object_type| (object_type, Existence::Created)),
+            Some(object_type) => Ok((object_type, Existence::Found)),
         }
     }
 
-    pub async fn get_object(
+    /// Compute what [Space::obtain_object_type] would create, including the recommended
+    /// relations that don't exist yet, without creating anything
+    pub async fn plan_obtain_object_type(
+        &self,
+        object_type_spec: &ObjectTypeSpec,
+    ) -> Result<CreationPlan, tonic::Status> {
+        let mut plan = CreationPlan::default();
+
+        if self.get_object_type(object_type_spec).await?.is_some() {
+            return Ok(plan);
+        }
+
+        for relation_spec in &object_type_spec.recommended_relations {
+            plan.extend(self.plan_obtain_relation(relation_spec).await?);
+        }
+        plan.object_types_to_create
+            .push(object_type_spec.name.clone());
+
+        Ok(plan)
+    }
+
+    /// Find every object with the spec's name and type
+    ///
+    /// Unlike [Space::get_object] this doesn't treat more than one match as an error, which suits
+    /// workflows where duplicates are expected such as quick captures that all share a name
+    pub async fn find_objects(
         &self,
         object_spec: &ObjectSpec,
-    ) -> Result<Option<Object>, tonic::Status> {
+    ) -> Result<Vec<Object>, tonic::Status> {
         use pb::models::block::content::dataview::filter::{Condition, Operator};
 
-        let mut objects = self
+        ensure_name_not_empty(&object_spec.name)?;
+
+        Ok(self
             .search_objects::<ObjectUnresolved>(vec![
                 Filter {
                     operator: Operator::And.into(),
                     relation_key: "name".to_string(),
-                    condition: Condition::Like.into(),
-                    value: Some(object_spec.name.clone().into_prost()),
-
-                    ..Default::default()
-                },
-                Filter {
-                    operator: Operator::And.into(),
-                    relation_key: "type".to_string(),
                     condition: Condition::Equal.into(),
-                    value: Some(object_spec.ty.id().into_prost()),
+                    value: Some(object_spec.name.clone().into_prost()),
 
                     ..Default::default()
                 },
+                Self::type_filter(&object_spec.ty),
             ])
-            .await?;
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// Get the single object with the spec's name and type
+    ///
+    /// This is the "exactly one" convenience over [Space::find_objects] and fails if more than one
+    /// object matches
+    pub async fn get_object(
+        &self,
+        object_spec: &ObjectSpec,
+    ) -> Result<Option<Object>, tonic::Status> {
+        let mut objects = self.find_objects(object_spec).await?;
 
         // This function is a bit unique compared to other gets in that it won't error if an object
         // exist with the same name but a different type. It feels like there are a lot of usecases
@@ -379,7 +1269,7 @@ Received recommended relations: {:?}",
         // shadowing comes in so handy in Rust.
         match objects.len() {
             0 => Ok(None),
-            1 => Ok(Some(objects.swap_remove(0).resolve(self.clone()))),
+            1 => Ok(Some(objects.swap_remove(0))),
             _ => Err(tonic::Status::failed_precondition(format!(
                 "More than one object with same name {}",
                 object_spec.name
@@ -387,89 +1277,1218 @@ Received recommended relations: {:?}",
         }
     }
 
-    pub async fn create_object(&self, object: ObjectDescription) -> Result<Object, tonic::Status> {
-        let response =
-            self.inner
-                .client
-                .grpc
-                .clone()
-                .object_create(RequestWithToken {
-                    request: pb::rpc::object::create::Request {
-                        space_id: self.inner.info.account_space_id.clone(),
-                        object_type_unique_key: object.ty.unique_key.clone().0,
-                        details: Some(object.try_into().map_err(|error| {
-                            tonic::Status::failed_precondition(format!("{error}"))
-                        })?),
+    /// Get the single object whose value for `relation` is exactly `value`
+    ///
+    /// Unlike [Space::get_object] this is keyed on a relation the caller knows to be unique, such
+    /// as an id from an external system, which makes it a better fit for idempotent imports
+    pub async fn get_object_by_relation(
+        &self,
+        relation: &Relation,
+        value: RelationValue,
+    ) -> Result<Option<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
 
-                        ..Default::default()
-                    },
-                    token: &self.inner.client.token,
-                })
-                .await?
-                .into_inner();
-
-        if let Some(error) = response.error {
-            use pb::rpc::object::create::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
-        }
+        let (key, value) = relation
+            .validate(value, self.inner.client.check_well_formed_values)
+            .map_err(|error| tonic::Status::failed_precondition(format!("{error}")))?
+            .into_raw_parts();
 
-        let Some(details) = response.details else {
-            return Err(tonic::Status::internal(
-                "anytype-heart did not respond with a object's details",
-            ));
-        };
+        let mut objects = self
+            .search_objects::<ObjectUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: key,
+                condition: Condition::Equal.into(),
+                value: Some(value),
 
-        ObjectUnresolved::try_from_prost(details)
-            .map(|object| object.resolve(self.clone()))
-            .map_err(|error| tonic::Status::internal(format!("{error}")))
-    }
+                ..Default::default()
+            }])
+            .await?;
 
-    pub async fn obtain_object(&self, object_spec: &ObjectSpec) -> Result<Object, tonic::Status> {
-        match self.get_object(object_spec).await? {
-            None => self.create_object(object_spec.as_description()).await,
-            Some(object) => Ok(object),
+        match objects.len() {
+            0 => Ok(None),
+            1 => Ok(Some(objects.swap_remove(0).resolve(self.clone()))),
+            _ => Err(tonic::Status::failed_precondition(format!(
+                "More than one object with the same value for relation {}",
+                relation.name()
+            ))),
         }
     }
 
-    pub(crate) async fn set_relation(
+    /// List every object of the given type in this space
+    ///
+    /// Objects that fail to convert are skipped with a warning, see [Space::list_objects_strict]
+    /// for a version that errors instead
+    pub async fn list_objects(&self, ty: &ObjectType) -> Result<Vec<Object>, tonic::Status> {
+        Ok(self
+            .search_objects::<ObjectUnresolved>(vec![Self::type_filter(ty)])
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// Subscribe to the objects of the given type in this space
+    ///
+    /// The stream starts with an [ObjectEvent::Snapshot] of the objects at the time of subscribing
+    /// followed by the objects added, removed and changed after it, so unlike listing the objects
+    /// and then watching them nothing in between is missed. A stream that falls too far behind
+    /// starts over with a new snapshot. Requires the client's event listener, see
+    /// [AnytypeClient::without_event_listener](crate::AnytypeClient::without_event_listener)
+    ///
+    /// The subscription is cancelled once the stream is dropped
+    pub async fn subscribe_objects(
         &self,
-        id: ObjectId,
-        detail: RelationDetail,
-    ) -> Result<(), tonic::Status> {
-        use pb::rpc::object::set_details::Detail;
+        ty: &ObjectType,
+    ) -> Result<SubscriptionStream<ObjectEvent>, tonic::Status> {
+        let Some(events) = &self.inner.client.events else {
+            return Err(tonic::Status::failed_precondition(
+                "Subscribing to objects requires the client's event listener",
+            ));
+        };
+        // Listen before subscribing so that no event in between is missed
+        let events = events.subscribe();
 
-        let (key, value) = detail.into_raw_parts();
+        let mut filters = vec![Self::type_filter(ty)];
+        self.scope_filters(
+            &mut filters,
+            ObjectUnresolved::LAYOUT
+                .iter()
+                .map(|layout| i32::from(*layout)),
+        );
+
+        let sub_id = next_sub_id();
         let response = self
             .inner
             .client
-            .grpc
-            .clone()
-            .object_set_details(RequestWithToken {
-                request: pb::rpc::object::set_details::Request {
-                    context_id: format!("{id}"),
-                    details: vec![Detail {
-                        key,
-                        value: Some(value),
-                    }],
+            .request(
+                pb::rpc::object::search_subscribe::Request {
+                    space_id: self.inner.space_id.clone(),
+                    sub_id: sub_id.clone(),
+                    filters,
+                    keys: vec!["id".to_string()],
+
+                    ..Default::default()
                 },
-                token: &self.inner.client.token,
+                |mut grpc, request| async move { grpc.object_search_subscribe(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let subscription = Subscription {
+            client: self.inner.client.clone(),
+            sub_id,
+        };
+
+        // The subscription only sends ids, the objects are read once it's in place so that any
+        // change made after reading them still arrives as an event
+        let ids = response
+            .records
+            .iter()
+            .map(|record| record_id(record).to_string())
+            .collect::<Vec<_>>();
+        let snapshot = self
+            .get_objects_by_id(ids.iter().filter_map(|id| id.parse::<ObjectId>().ok()))
+            .await?;
+        let matched = ids.into_iter().collect::<HashSet<_>>();
+
+        let stream = ObjectSubscription::new(
+            self.clone(),
+            ty.clone(),
+            &subscription,
+            events,
+            matched,
+            snapshot,
+        )
+        .into_stream();
+
+        Ok(SubscriptionStream::new(stream, subscription))
+    }
+
+    /// Same as [Space::list_objects] but gives up as soon as `cancelled` completes, for example
+    /// with a `tokio_util` `CancellationToken::cancelled()`
+    ///
+    /// Giving up drops the in-flight search request and fails with [tonic::Code::Cancelled], no
+    /// partial results are returned. Dropping the future returned by any of the searches is just
+    /// as safe, this is a convenience for when the cancellation signal lives elsewhere
+    pub async fn list_objects_cancellable(
+        &self,
+        ty: &ObjectType,
+        cancelled: impl Future<Output = ()>,
+    ) -> Result<Vec<Object>, tonic::Status> {
+        let cancelled = pin!(cancelled);
+        let objects = pin!(self.list_objects(ty));
+
+        match select(cancelled, objects).await {
+            Either::Left(_) => Err(tonic::Status::cancelled("Listing objects was cancelled")),
+            Either::Right((objects, _)) => objects,
+        }
+    }
+
+    /// List every object in this space that is marked as a favorite, see [Object::set_favorite]
+    pub async fn list_favorite_objects(&self) -> Result<Vec<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        Ok(self
+            .search_objects::<ObjectUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "isFavorite".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(true.into_prost()),
+
+                ..Default::default()
+            }])
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// List every object in this space with any of the given layouts, regardless of its type
+    ///
+    /// Unlike the other searches this isn't limited to the layouts [Object] usually covers, so
+    /// it can for example return notes, tasks and bookmarks together. Objects that fail to
+    /// convert are skipped with a warning
+    pub async fn list_objects_with_layouts(
+        &self,
+        layouts: &[ObjectLayout],
+    ) -> Result<Vec<Object>, tonic::Status> {
+        Ok(self
+            .search_records_in_layouts(
+                Vec::new(),
+                Vec::new(),
+                layouts.iter().map(|layout| layout.into_raw()),
+            )
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                let id = record_id(&record).to_string();
+
+                match ObjectUnresolved::try_from_any_layout(record) {
+                    Ok(object) => Some(object.resolve(self.clone())),
+                    Err(error) => {
+                        tracing::warn!(%id, %error, "dropping search record that failed conversion");
+                        None
+                    }
+                }
             })
+            .collect())
+    }
+
+    /// Count the objects of the given type in this space without fetching their details
+    pub async fn count_objects(&self, ty: &ObjectType) -> Result<u64, tonic::Status> {
+        let records = self
+            .search_records::<ObjectUnresolved>(
+                vec![Self::type_filter(ty)],
+                // isHidden is needed to leave out hidden objects like every other search does
+                vec!["id".to_string(), "isHidden".to_string()],
+            )
+            .await?;
+
+        Ok(records.len() as u64)
+    }
+
+    /// List every object of the given type in this space, failing with the first object that
+    /// couldn't be converted instead of skipping it
+    pub async fn list_objects_strict(&self, ty: &ObjectType) -> Result<Vec<Object>, tonic::Status> {
+        Ok(self
+            .search_objects_strict::<ObjectUnresolved>(vec![Self::type_filter(ty)])
             .await?
-            .into_inner();
-
-        if let Some(error) = response.error {
-            use pb::rpc::object::set_details::response::error::Code;
-            match error.code() {
-                Code::Null => {}
-                Code::UnknownError => return Err(tonic::Status::unknown(error.description)),
-                Code::BadInput => return Err(tonic::Status::invalid_argument(error.description)),
-            }
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// List every object in this space that was modified after the given time
+    ///
+    /// Objects that have never been modified and thus have no modified date are not included
+    pub async fn objects_modified_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        Ok(self
+            .search_objects::<ObjectUnresolved>(vec![
+                Filter {
+                    operator: Operator::And.into(),
+                    relation_key: "lastModifiedDate".to_string(),
+                    condition: Condition::Exists.into(),
+
+                    ..Default::default()
+                },
+                Filter {
+                    operator: Operator::And.into(),
+                    relation_key: "lastModifiedDate".to_string(),
+                    condition: Condition::Greater.into(),
+                    value: Some((since.timestamp() as f64).into_prost()),
+
+                    ..Default::default()
+                },
+            ])
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    fn type_filter(ty: &ObjectType) -> Filter {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        Filter {
+            operator: Operator::And.into(),
+            relation_key: "type".to_string(),
+            condition: Condition::Equal.into(),
+            value: Some(ty.id().into_prost()),
+
+            ..Default::default()
+        }
+    }
+
+    pub async fn create_object(&self, object: ObjectDescription) -> Result<Object, tonic::Status> {
+        self.ensure_writable()?;
+
+        let template_id = match object.ty.default_template {
+            Some(id) if object.use_default_template => format!("{id}"),
+            _ => String::new(),
+        };
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::create::Request {
+                    space_id: self.inner.space_id.clone(),
+                    object_type_unique_key: object.ty.unique_key.clone().0,
+                    template_id,
+                    details: Some(
+                        object
+                            .into_struct(self.inner.client.check_well_formed_values)
+                            .map_err(|error| {
+                                tonic::Status::failed_precondition(format!("{error}"))
+                            })?,
+                    ),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_create(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let Some(details) = response.details else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with a object's details",
+            ));
+        };
+
+        ObjectUnresolved::try_from_prost(details)
+            .map(|object| object.resolve(self.clone()))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    /// Create an object unless one with the same value for the `key` relation already exists,
+    /// which makes retrying a create whose response got lost safe
+    ///
+    /// `object` must set a value for `key`, which should be unique per object like an id from the
+    /// system the objects are imported from
+    pub async fn create_object_with_key(
+        &self,
+        object: ObjectDescription,
+        key: &Relation,
+    ) -> Result<(Object, Existence), tonic::Status> {
+        let Some(value) = object.relations.get(key).cloned() else {
+            return Err(tonic::Status::invalid_argument(format!(
+                "Object `{}` has no value for its key relation {}",
+                object.name,
+                key.name()
+            )));
+        };
+
+        match self.get_object_by_relation(key, value).await? {
+            Some(object) => Ok((object, Existence::Found)),
+            None => self
+                .create_object(object)
+                .await
+                .map(|object| (object, Existence::Created)),
+        }
+    }
+
+    pub(crate) async fn templates(&self, ty: &ObjectType) -> Result<Vec<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        Ok(self
+            .search_objects::<ObjectUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "targetObjectType".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(ty.id().into_prost()),
+
+                ..Default::default()
+            }])
+            .await?
+            .into_iter()
+            .map(|object| object.resolve(self.clone()))
+            .collect())
+    }
+
+    /// Create an empty template for objects of the given type
+    pub(crate) async fn create_template(
+        &self,
+        ty: &ObjectType,
+        name: &str,
+    ) -> Result<Object, tonic::Status> {
+        self.ensure_writable()?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::create::Request {
+                    space_id: self.inner.space_id.clone(),
+                    object_type_unique_key: TEMPLATE_TYPE_UNIQUE_KEY.to_string(),
+                    details: Some(prost_types::Struct {
+                        fields: BTreeMap::from([
+                            ("name".to_string(), name.to_string().into_prost()),
+                            ("targetObjectType".to_string(), ty.id().into_prost()),
+                        ]),
+                    }),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_create(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let Some(details) = response.details else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with a template's details",
+            ));
+        };
+
+        ObjectUnresolved::try_from_prost(details)
+            .map(|object| object.resolve(self.clone()))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    pub(crate) async fn object_types_recommending(
+        &self,
+        relation: &Relation,
+    ) -> Result<Vec<ObjectType>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let object_types = self
+            .search_objects::<ObjectTypeUnresolved>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "recommendedRelations".to_string(),
+                condition: Condition::In.into(),
+                value: Some(vec![relation.id().into_prost()].into_prost()),
+
+                ..Default::default()
+            }])
+            .await?;
+
+        let mut output = Vec::with_capacity(object_types.len());
+        for object_type in object_types {
+            output.push(object_type.slow_resolve(self.clone()).await?);
         }
 
+        Ok(output)
+    }
+
+    /// Create an object named `name` pre-filled from a template, see [ObjectType::templates]
+    pub async fn create_object_from_template(
+        &self,
+        template: &Object,
+        name: &str,
+    ) -> Result<Object, tonic::Status> {
+        self.ensure_writable()?;
+
+        let Some(RawValue::String(target_type)) = template.get_raw("targetObjectType") else {
+            return Err(tonic::Status::failed_precondition(format!(
+                "Object `{}` is not a template",
+                template.name()
+            )));
+        };
+        let target_type =
+            ObjectTypeId::try_from_prost(prost_types::value::Kind::StringValue(target_type))
+                .map_err(|error| tonic::Status::internal(format!("{error}")))?;
+
+        let Some(object_type) = self
+            .get_objects::<ObjectTypeUnresolved>([target_type])
+            .await?
+            .pop()
+        else {
+            return Err(tonic::Status::not_found(format!(
+                "Object type {} of template `{}` was not found",
+                target_type,
+                template.name()
+            )));
+        };
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::create::Request {
+                    space_id: self.inner.space_id.clone(),
+                    object_type_unique_key: object_type.unique_key.0,
+                    template_id: format!("{}", template.id()),
+                    details: Some(prost_types::Struct {
+                        fields: BTreeMap::from([(
+                            "name".to_string(),
+                            name.to_string().into_prost(),
+                        )]),
+                    }),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_create(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let Some(details) = response.details else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with a object's details",
+            ));
+        };
+
+        ObjectUnresolved::try_from_prost(details)
+            .map(|object| object.resolve(self.clone()))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    /// Compute whether [Space::obtain_object] would create the object without creating anything
+    pub async fn plan_obtain_object(
+        &self,
+        object_spec: &ObjectSpec,
+    ) -> Result<CreationPlan, tonic::Status> {
+        let mut plan = CreationPlan::default();
+
+        if self.get_object(object_spec).await?.is_none() {
+            plan.objects_to_create.push(object_spec.name.clone());
+        }
+
+        Ok(plan)
+    }
+
+    /// Create a bookmark object for the given url with its title and description fetched from
+    /// the url
+    ///
+    /// If fetching the url's preview fails the bookmark is still created with only its url and the
+    /// failure is returned in [CreatedBookmark::preview_error]
+    pub async fn create_bookmark(&self, url: &str) -> Result<CreatedBookmark, tonic::Status> {
+        self.ensure_writable()?;
+
+        let mut details = BTreeMap::from([("source".to_string(), url.to_string().into_prost())]);
+
+        let preview_error = match self.link_preview(url).await {
+            Ok(preview) => {
+                if !preview.title.is_empty() {
+                    details.insert("name".to_string(), preview.title.into_prost());
+                }
+                if !preview.description.is_empty() {
+                    details.insert("description".to_string(), preview.description.into_prost());
+                }
+                None
+            }
+            Err(error) => Some(error),
+        };
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::create_bookmark::Request {
+                    space_id: self.inner.space_id.clone(),
+                    details: Some(prost_types::Struct { fields: details }),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_create_bookmark(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let Some(details) = response.details else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with a bookmark's details",
+            ));
+        };
+
+        let object = ObjectUnresolved::try_from_prost(details)
+            .map(|object| object.resolve(self.clone()))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))?;
+
+        Ok(CreatedBookmark {
+            object,
+            preview_error,
+        })
+    }
+
+    async fn link_preview(&self, url: &str) -> Result<pb::models::LinkPreview, tonic::Status> {
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::link_preview::Request {
+                    url: url.to_string(),
+                },
+                |mut grpc, request| async move { grpc.link_preview(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        response.link_preview.ok_or_else(|| {
+            tonic::Status::internal("anytype-heart did not respond with a link preview")
+        })
+    }
+
+    pub async fn obtain_object(&self, object_spec: &ObjectSpec) -> Result<Object, tonic::Status> {
+        self.obtain_object_with_existence(object_spec)
+            .await
+            .map(|(object, _)| object)
+    }
+
+    /// Like [Space::obtain_object] but also reports whether the object was created or found
+    pub async fn obtain_object_with_existence(
+        &self,
+        object_spec: &ObjectSpec,
+    ) -> Result<(Object, Existence), tonic::Status> {
+        match self.get_object(object_spec).await? {
+            None => self
+                .create_object(object_spec.as_description())
+                .await
+                .map(|object| (object, Existence::Created)),
+            Some(object) => Ok((object, Existence::Found)),
+        }
+    }
+
+    /// Create an object named `name` of the object type `type_name` with the given relation
+    /// values, obtaining the object type first
+    ///
+    /// The object type is obtained with exactly the relations given here as its recommended
+    /// relations, so an existing object type of that name with other recommended relations is an
+    /// error just like with [Space::obtain_object_type]. A new object is created on every call
+    pub async fn quick_create(
+        &self,
+        type_name: &str,
+        relations: HashMap<RelationSpec, RelationValue>,
+        name: &str,
+    ) -> Result<Object, tonic::Status> {
+        let object_type = self
+            .obtain_object_type(&ObjectTypeSpec::new(type_name, relations.keys().cloned()))
+            .await?;
+
+        let recommended_relations = object_type
+            .recommended_relations()
+            .iter()
+            .map(|relation| (relation.as_spec(), relation.clone()))
+            .collect::<HashMap<_, _>>();
+        let relations = relations
+            .into_iter()
+            .map(|(spec, value)| match recommended_relations.get(&spec) {
+                Some(relation) => Ok((relation.clone(), value)),
+                None => Err(tonic::Status::internal(format!(
+                    "ObjectType `{object_type}` is missing its recommended relation {}",
+                    spec.name
+                ))),
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        self.create_object(ObjectDescription {
+            relations,
+            ..ObjectSpec::new(object_type, name).as_description()
+        })
+        .await
+    }
+
+    /// Like [Space::obtain_object] but for many specs at once
+    ///
+    /// Existing objects are looked up with a single search over all of the specs' names and only
+    /// the missing ones are created, concurrently. The returned objects are in the same order as
+    /// the specs, and repeated specs resolve to the same object.
+    ///
+    /// A failure to create an object is reported with the name of the spec that caused it.
+    pub async fn obtain_objects(
+        &self,
+        specs: Vec<ObjectSpec>,
+    ) -> Result<Vec<Object>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        for spec in &specs {
+            ensure_name_not_empty(&spec.name)?;
+        }
+
+        let mut objects = HashMap::<(String, ObjectTypeId), Vec<Object>>::new();
+        for batch in specs.chunks(GET_OBJECTS_BATCH_SIZE) {
+            let found = self
+                .search_objects::<ObjectUnresolved>(vec![
+                    Filter {
+                        operator: Operator::And.into(),
+                        relation_key: "name".to_string(),
+                        condition: Condition::In.into(),
+                        value: Some(
+                            batch
+                                .iter()
+                                .map(|spec| spec.name.clone().into_prost())
+                                .collect::<Vec<_>>()
+                                .into_prost(),
+                        ),
+
+                        ..Default::default()
+                    },
+                    Filter {
+                        operator: Operator::And.into(),
+                        relation_key: "type".to_string(),
+                        condition: Condition::In.into(),
+                        value: Some(
+                            batch
+                                .iter()
+                                .map(|spec| spec.ty.id().into_prost())
+                                .collect::<Vec<_>>()
+                                .into_prost(),
+                        ),
+
+                        ..Default::default()
+                    },
+                ])
+                .await?;
+
+            for object in found {
+                let object = object.resolve(self.clone());
+                objects
+                    .entry((object.name(), object.type_id()))
+                    .or_default()
+                    .push(object);
+            }
+        }
+
+        // Same as in get_object, an object with the same name but a different type doesn't count
+        let mut missing = Vec::new();
+        for spec in &specs {
+            let key = (spec.name.clone(), spec.ty.id());
+            match objects.get(&key).map(Vec::len) {
+                Some(1) => {}
+                Some(_) => {
+                    return Err(tonic::Status::failed_precondition(format!(
+                        "More than one object with same name {}",
+                        spec.name
+                    )))
+                }
+                None => {
+                    if !missing.iter().any(|(missing_key, _)| missing_key == &key) {
+                        missing.push((key, spec));
+                    }
+                }
+            }
+        }
+
+        let created = stream::iter(missing.into_iter().map(|(key, spec)| async move {
+            match self.create_object(spec.as_description()).await {
+                Ok(object) => Ok((key, vec![object])),
+                Err(status) => Err(tonic::Status::new(
+                    status.code(),
+                    format!(
+                        "Failed to create object {}: {}",
+                        spec.name,
+                        status.message()
+                    ),
+                )),
+            }
+        }))
+        .buffer_unordered(self.inner.client.max_concurrent_requests)
+        .try_collect::<Vec<_>>()
+        .await?;
+        objects.extend(created);
+
+        Ok(specs
+            .iter()
+            .map(|spec| objects[&(spec.name.clone(), spec.ty.id())][0].clone())
+            .collect())
+    }
+
+    pub(crate) async fn clear_relation(
+        &self,
+        id: ObjectId,
+        relation: &Relation,
+    ) -> Result<(), tonic::Status> {
+        self.set_detail(
+            id,
+            relation.relation_key.0.clone(),
+            RawValue::Null.into_prost(),
+        )
+        .await
+    }
+
+    /// Set a single detail on an object without any validation, callers are expected to have
+    /// validated the value
+    pub(crate) async fn set_detail(
+        &self,
+        id: ObjectId,
+        key: String,
+        value: prost_types::Value,
+    ) -> Result<(), tonic::Status> {
+        use pb::rpc::object::set_details::Detail;
+
+        self.ensure_writable()?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::set_details::Request {
+                    context_id: format!("{id}"),
+                    details: vec![Detail {
+                        key,
+                        value: Some(value),
+                    }],
+                },
+                |mut grpc, request| async move { grpc.object_set_details(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    /// Ask anytype-heart to emit detail events for the given keys of a single object
+    ///
+    /// The subscription is cancelled once the returned [Subscription] is dropped
+    pub(crate) async fn subscribe_object(
+        &self,
+        id: ObjectId,
+        keys: Vec<String>,
+    ) -> Result<Subscription, tonic::Status> {
+        let sub_id = next_sub_id();
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::subscribe_ids::Request {
+                    space_id: self.inner.space_id.clone(),
+                    sub_id: sub_id.clone(),
+                    ids: vec![format!("{id}")],
+                    keys,
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_subscribe_ids(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(Subscription {
+            client: self.inner.client.clone(),
+            sub_id,
+        })
+    }
+
+    /// List the versions of an object anytype-heart recorded, newest first
+    pub(crate) async fn object_versions(
+        &self,
+        id: ObjectId,
+    ) -> Result<Vec<ObjectVersion>, tonic::Status> {
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::history::get_versions::Request {
+                    object_id: format!("{id}"),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.history_get_versions(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        response
+            .versions
+            .into_iter()
+            .map(ObjectVersion::try_from)
+            .collect()
+    }
+
+    /// Get an object as it was at the given version
+    pub(crate) async fn object_at_version(
+        &self,
+        id: ObjectId,
+        version: &ObjectVersion,
+    ) -> Result<ObjectAtVersion, tonic::Status> {
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::history::show_version::Request {
+                    object_id: format!("{id}"),
+                    version_id: version.id().to_string(),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.history_show_version(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        let id = format!("{id}");
+        let Some(details) = response
+            .object_view
+            .into_iter()
+            .flat_map(|view| view.details)
+            .find(|details| details.id == id)
+            .and_then(|details| details.details)
+        else {
+            return Err(tonic::Status::internal(
+                "anytype-heart did not respond with the object's details at that version",
+            ));
+        };
+
+        ObjectUnresolved::try_from_prost(details)
+            .map(|object| ObjectAtVersion::new(object.resolve(self.clone()), version.clone()))
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    pub(crate) async fn export_objects(
+        &self,
+        object_ids: Vec<ObjectId>,
+        format: ExportFormat,
+    ) -> Result<Vec<u8>, tonic::Status> {
+        let export_dir = temporary_export_dir();
+        std::fs::create_dir_all(&export_dir).map_err(|error| {
+            tonic::Status::internal(format!("Failed to create export directory: {error}"))
+        })?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::list_export::Request {
+                    space_id: self.inner.space_id.clone(),
+                    path: export_dir.to_string_lossy().into_owned(),
+                    object_ids: object_ids.into_iter().map(|id| format!("{id}")).collect(),
+                    format: pb::models::export::Format::from(format).into(),
+                    zip: true,
+                    include_nested: false,
+                    include_files: true,
+                    is_json: false,
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_list_export(request).await },
+            )
+            .await;
+
+        let result = Self::read_export(response);
+        let _ = std::fs::remove_dir_all(&export_dir);
+
+        result
+    }
+
+    fn read_export(
+        response: Result<pb::rpc::object::list_export::Response, tonic::Status>,
+    ) -> Result<Vec<u8>, tonic::Status> {
+        let response = response?;
+
+        check_response_error(response.error)?;
+
+        std::fs::read(&response.path).map_err(|error| {
+            tonic::Status::internal(format!(
+                "Failed to read export archive {}: {error}",
+                response.path
+            ))
+        })
+    }
+
+    /// Export every object in this space as a zip archive in the given format
+    pub async fn export_all(&self, format: ExportFormat) -> Result<Vec<u8>, tonic::Status> {
+        // anytype-heart exports the whole space when it's not given any object ids
+        self.export_objects(Vec::new(), format).await
+    }
+
+    /// Import objects from an archive or directory at the given path into this space
+    ///
+    /// Any failure aborts the whole import, see [Space::import_with_mode] to skip over the objects
+    /// that fail instead
+    pub async fn import(
+        &self,
+        path: &Path,
+        format: ImportFormat,
+    ) -> Result<ImportReport, tonic::Status> {
+        self.import_with_mode(path, format, ImportMode::AllOrNothing)
+            .await
+    }
+
+    /// Same as [Space::import] but with a choice of how objects that fail to import are treated
+    pub async fn import_with_mode(
+        &self,
+        path: &Path,
+        format: ImportFormat,
+        mode: ImportMode,
+    ) -> Result<ImportReport, tonic::Status> {
+        self.ensure_writable()?;
+
+        let Some(path) = path.to_str() else {
+            return Err(tonic::Status::invalid_argument(format!(
+                "Import path {} is not valid UTF-8",
+                path.display()
+            )));
+        };
+        let (ty, params) = format.into_request_parts(path.to_string());
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::import::Request {
+                    space_id: self.inner.space_id.clone(),
+                    r#type: ty.into(),
+                    mode: pb::rpc::object::import::request::Mode::from(mode).into(),
+                    no_progress: true,
+                    params: Some(params),
+
+                    ..Default::default()
+                },
+                |mut grpc, request| async move { grpc.object_import(request).await },
+            )
+            .await?;
+
+        // When errors are ignored anytype-heart still responds with them, along with whatever it
+        // managed to import
+        let skipped = match check_response_error(response.error) {
+            Ok(()) => None,
+            Err(status) if mode == ImportMode::IgnoreErrors && response.objects_count > 0 => {
+                Some(status)
+            }
+            Err(status) => return Err(status),
+        };
+
+        let collection = if response.collection_id.is_empty() {
+            None
+        } else {
+            Some(
+                ObjectId::try_from_prost(prost_types::value::Kind::StringValue(
+                    response.collection_id,
+                ))
+                .map_err(|error| tonic::Status::internal(format!("{error}")))?,
+            )
+        };
+        let objects = match collection {
+            Some(collection) => self.collection_links(collection).await?,
+            None => Vec::new(),
+        };
+
+        Ok(ImportReport {
+            collection,
+            objects,
+            objects_count: response.objects_count as u64,
+            skipped,
+        })
+    }
+
+    /// The ids of the objects in a collection, which anytype-heart keeps in its `links` detail
+    async fn collection_links(&self, collection: ObjectId) -> Result<Vec<ObjectId>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        let Some(record) = self
+            .search_all_records(
+                vec![Filter {
+                    operator: Operator::And.into(),
+                    relation_key: "id".to_string(),
+                    condition: Condition::Equal.into(),
+                    value: Some(collection.into_prost()),
+
+                    ..Default::default()
+                }],
+                vec!["id".to_string(), "links".to_string()],
+            )
+            .await?
+            .pop()
+        else {
+            return Err(tonic::Status::not_found(format!(
+                "Collection {collection} of the import was not found"
+            )));
+        };
+
+        ProstStruct::from(record)
+            .take_optional::<Vec<ObjectId>>("links")
+            .map(Option::unwrap_or_default)
+            .map_err(|error| tonic::Status::internal(format!("{error}")))
+    }
+
+    pub(crate) async fn set_object_type(
+        &self,
+        id: ObjectId,
+        ty: &ObjectType,
+    ) -> Result<(), tonic::Status> {
+        self.ensure_writable()?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::set_object_type::Request {
+                    context_id: format!("{id}"),
+                    object_type_unique_key: ty.unique_key.0.clone(),
+                },
+                |mut grpc, request| async move { grpc.object_set_object_type(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    /// Delete objects for good, rather than moving them to the bin
+    pub(crate) async fn delete_objects(
+        &self,
+        ids: impl IntoIterator<Item = ObjectId>,
+    ) -> Result<(), tonic::Status> {
+        self.ensure_writable()?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::list_delete::Request {
+                    object_ids: ids.into_iter().map(|id| format!("{id}")).collect(),
+                },
+                |mut grpc, request| async move { grpc.object_list_delete(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    pub(crate) async fn set_object_favorite(
+        &self,
+        id: ObjectId,
+        is_favorite: bool,
+    ) -> Result<(), tonic::Status> {
+        self.ensure_writable()?;
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::object::set_is_favorite::Request {
+                    context_id: format!("{id}"),
+                    is_favorite,
+                },
+                |mut grpc, request| async move { grpc.object_set_is_favorite(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(())
+    }
+
+    /// List the active members of this space, including its owner
+    pub async fn members(&self) -> Result<Vec<SpaceMember>, tonic::Status> {
+        use pb::models::ParticipantStatus;
+
+        Ok(self
+            .search_objects::<SpaceMember>(Vec::new())
+            .await?
+            .into_iter()
+            .filter(|member| member.status == ParticipantStatus::Active)
+            .collect())
+    }
+
+    /// Get the member of this space with the given account identity, whatever their status
+    pub async fn member(&self, identity: &str) -> Result<Option<SpaceMember>, tonic::Status> {
+        use pb::models::block::content::dataview::filter::{Condition, Operator};
+
+        Ok(self
+            .search_objects::<SpaceMember>(vec![Filter {
+                operator: Operator::And.into(),
+                relation_key: "identity".to_string(),
+                condition: Condition::Equal.into(),
+                value: Some(identity.to_string().into_prost()),
+
+                ..Default::default()
+            }])
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    /// The permission the account this space was opened with has in it
+    pub async fn my_permission(&self) -> Result<Permission, tonic::Status> {
+        match self.member(&self.inner.identity).await? {
+            Some(member) => Ok(member.permission()),
+            None => Err(tonic::Status::not_found(
+                "anytype-heart has no record of this account being a member of the space",
+            )),
+        }
+    }
+
+    /// Generate a link others can use to request to join this space
+    ///
+    /// Fails with [SharingRequiresNetworkSync] unless the client uses [NetworkSync::Sync]
+    pub async fn generate_invite(&self) -> Result<InviteLink, tonic::Status> {
+        self.ensure_writable()?;
+
+        if self.inner.client.network_sync != NetworkSync::Sync {
+            return Err(SharingRequiresNetworkSync.into());
+        }
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::space::invite_generate::Request {
+                    space_id: self.inner.space_id.clone(),
+                },
+                |mut grpc, request| async move { grpc.space_invite_generate(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
+        Ok(InviteLink::new(
+            response.invite_cid,
+            response.invite_file_key,
+        ))
+    }
+
+    /// Revoke the current invite link of this space so it can no longer be used to join
+    pub async fn revoke_invite(&self) -> Result<(), tonic::Status> {
+        self.ensure_writable()?;
+
+        if self.inner.client.network_sync != NetworkSync::Sync {
+            return Err(SharingRequiresNetworkSync.into());
+        }
+
+        let response = self
+            .inner
+            .client
+            .request(
+                pb::rpc::space::invite_revoke::Request {
+                    space_id: self.inner.space_id.clone(),
+                },
+                |mut grpc, request| async move { grpc.space_invite_revoke(request).await },
+            )
+            .await?;
+
+        check_response_error(response.error)?;
+
         Ok(())
     }
 }
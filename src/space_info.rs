@@ -0,0 +1,48 @@
+use crate::pb;
+
+/// Details anytype-heart keeps about a space and the account and device it was opened with
+#[derive(Debug, Clone)]
+pub struct SpaceInfo(pub(crate) pb::models::account::Info);
+
+impl SpaceInfo {
+    pub fn space_id(&self) -> &str {
+        &self.0.account_space_id
+    }
+
+    /// The id of the object holding the account's profile
+    pub fn profile_object_id(&self) -> &str {
+        &self.0.profile_object_id
+    }
+
+    /// The id of the object shown when opening the space
+    pub fn home_object_id(&self) -> &str {
+        &self.0.home_object_id
+    }
+
+    /// The id of the object archived objects are collected under
+    pub fn archive_object_id(&self) -> &str {
+        &self.0.archive_object_id
+    }
+
+    pub fn device_id(&self) -> &str {
+        &self.0.device_id
+    }
+
+    pub fn analytics_id(&self) -> &str {
+        &self.0.analytics_id
+    }
+
+    pub fn network_id(&self) -> &str {
+        &self.0.network_id
+    }
+
+    /// The address of anytype-heart's gateway that serves files and media over http
+    pub fn gateway_url(&self) -> &str {
+        &self.0.gateway_url
+    }
+
+    /// Where anytype-heart stores this account's data on disk
+    pub fn local_storage_path(&self) -> &str {
+        &self.0.local_storage_path
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+
+use crate::object::{Object, ObjectId};
+use crate::object_type::ObjectType;
+use crate::pb::event::message::Value;
+use crate::space::{Space, Subscription};
+
+/// A change to the objects matched by [Space::subscribe_objects]
+#[derive(Debug, Clone)]
+pub enum ObjectEvent {
+    /// The objects matched at the time of subscribing, always the first event
+    ///
+    /// Sent again with the objects matched at that point whenever the stream fell too far behind
+    /// anytype-heart's events and some of them were lost, replacing everything known before it
+    Snapshot(Vec<Object>),
+    /// An object started matching the subscription
+    Add(Object),
+    /// An object stopped matching the subscription, either because it was deleted or because it
+    /// no longer fits it
+    Remove(ObjectId),
+    /// The details of a matched object changed, carries the object as it is after the change
+    Change(Object),
+}
+
+/// A stream of changes backed by an anytype-heart subscription, returned by
+/// [Space::subscribe_objects] and [Object::watch](crate::Object::watch)
+///
+/// With the `background-tasks` feature the subscription is cancelled when the stream is dropped.
+/// Without it, call [SubscriptionStream::cancel] once done so anytype-heart stops sending events
+pub struct SubscriptionStream<T> {
+    stream: Pin<Box<dyn Stream<Item = T> + Send>>,
+    subscription: Subscription,
+}
+
+impl<T> SubscriptionStream<T> {
+    pub(crate) fn new(
+        stream: impl Stream<Item = T> + Send + 'static,
+        subscription: Subscription,
+    ) -> Self {
+        Self {
+            stream: Box::pin(stream),
+            subscription,
+        }
+    }
+
+    /// Cancel the subscription, waiting for anytype-heart to stop it
+    pub async fn cancel(self) -> Result<(), tonic::Status> {
+        self.subscription.cancel().await
+    }
+}
+
+impl<T> Stream for SubscriptionStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> std::fmt::Debug for SubscriptionStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionStream")
+            .field("sub_id", &self.subscription.sub_id())
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) struct ObjectSubscription {
+    space: Space,
+    /// The type whose objects are subscribed to, to take a new snapshot of after falling behind
+    ty: ObjectType,
+    sub_id: String,
+    events: tokio::sync::broadcast::Receiver<Value>,
+    /// The ids of the objects currently matched, as anytype-heart sends them
+    matched: HashSet<String>,
+    /// The snapshot that is yet to be yielded
+    snapshot: Option<Vec<Object>>,
+}
+
+impl ObjectSubscription {
+    pub(crate) fn new(
+        space: Space,
+        ty: ObjectType,
+        subscription: &Subscription,
+        events: tokio::sync::broadcast::Receiver<Value>,
+        matched: HashSet<String>,
+        snapshot: Vec<Object>,
+    ) -> Self {
+        Self {
+            space,
+            ty,
+            sub_id: subscription.sub_id().to_string(),
+            events,
+            matched,
+            snapshot: Some(snapshot),
+        }
+    }
+
+    pub(crate) fn into_stream(self) -> impl Stream<Item = ObjectEvent> + Send {
+        futures_util::stream::unfold(self, |mut subscription| async move {
+            let event = subscription.next().await?;
+            Some((event, subscription))
+        })
+    }
+
+    async fn next(&mut self) -> Option<ObjectEvent> {
+        use tokio::sync::broadcast::error::RecvError;
+
+        if let Some(snapshot) = self.snapshot.take() {
+            return Some(ObjectEvent::Snapshot(snapshot));
+        }
+
+        loop {
+            let event = match self.events.recv().await {
+                Ok(event) => event,
+                // The skipped events can't be replayed so the matched objects are read again
+                // instead, ending the stream if that fails rather than carrying on out of sync
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        sub_id = %self.sub_id,
+                        skipped,
+                        "object subscription fell behind on events, taking a new snapshot"
+                    );
+                    return match self.space.list_objects(&self.ty).await {
+                        Ok(objects) => {
+                            self.matched = objects
+                                .iter()
+                                .map(|object| object.id().to_string())
+                                .collect();
+                            Some(ObjectEvent::Snapshot(objects))
+                        }
+                        Err(error) => {
+                            tracing::warn!(
+                                sub_id = %self.sub_id,
+                                %error,
+                                "failed to take a new snapshot, ending object subscription"
+                            );
+                            None
+                        }
+                    };
+                }
+                Err(RecvError::Closed) => return None,
+            };
+
+            let (id, added) = match event {
+                Value::SubscriptionAdd(add) if add.sub_id == self.sub_id => {
+                    if !self.matched.insert(add.id.clone()) {
+                        continue;
+                    }
+                    (add.id, true)
+                }
+                Value::SubscriptionRemove(remove) if remove.sub_id == self.sub_id => {
+                    if !self.matched.remove(&remove.id) {
+                        continue;
+                    }
+                    match remove.id.parse() {
+                        Ok(id) => return Some(ObjectEvent::Remove(id)),
+                        Err(_) => continue,
+                    }
+                }
+                // Details of objects that are about to be added arrive before their add event
+                // and are picked up by reading the object then
+                Value::ObjectDetailsSet(set) if self.is_matched(&set.sub_ids, &set.id) => {
+                    (set.id, false)
+                }
+                Value::ObjectDetailsAmend(amend) if self.is_matched(&amend.sub_ids, &amend.id) => {
+                    (amend.id, false)
+                }
+                Value::ObjectDetailsUnset(unset) if self.is_matched(&unset.sub_ids, &unset.id) => {
+                    (unset.id, false)
+                }
+                _ => continue,
+            };
+
+            let Ok(id) = id.parse::<ObjectId>() else {
+                continue;
+            };
+
+            // Events only carry the details that changed, so the object is read again for the
+            // event to carry all of them
+            match self.space.get_objects_by_id([id]).await {
+                Ok(mut objects) => match objects.pop() {
+                    Some(object) if added => return Some(ObjectEvent::Add(object)),
+                    Some(object) => return Some(ObjectEvent::Change(object)),
+                    None => continue,
+                },
+                Err(error) => {
+                    tracing::warn!(%id, %error, "failed to read subscribed object");
+                }
+            }
+        }
+    }
+
+    fn is_matched(&self, sub_ids: &[String], id: &str) -> bool {
+        sub_ids.contains(&self.sub_id) && self.matched.contains(id)
+    }
+}
@@ -1,7 +1,7 @@
 mod utils;
 
-use anytype_friend::{AnytypeClient, NetworkSync};
-use utils::run_with_service;
+use anytype_friend::{AnytypeClient, AuthError, NetworkSync, RawValue, TokenExpired};
+use utils::{capture_logs, run_with_service};
 
 #[tokio::test]
 async fn can_create_an_account_and_authenticate_with_it() {
@@ -33,6 +33,361 @@ async fn can_create_an_account_and_authenticate_with_it() {
             .unwrap();
 
         assert_eq!(client.account().id, account_id);
+        client.ping().await.unwrap();
     })
     .await;
 }
+
+#[tokio::test]
+async fn can_create_an_account_from_a_known_mnemonic() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+    let other_temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let other_temp_dir_path = other_temp_dir.path();
+
+    let (mnemonic, account_id) = run_with_service(|port| async move {
+        let (mnemonic, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        (mnemonic, client.account().id.clone())
+    })
+    .await;
+
+    run_with_service(|port| async move {
+        let client = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(other_temp_dir_path)
+            .create_account_with_mnemonic(&mnemonic, "Test Client")
+            .await
+            .unwrap();
+
+        assert_eq!(client.account().id, account_id);
+        client.ping().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_authenticate_without_an_event_listener() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    let (mnemonic, account_id) = run_with_service(|port| async move {
+        let (mnemonic, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::LocalOnly)
+            .with_root_path(temp_dir_path)
+            .without_event_listener()
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        (mnemonic, client.account().id.clone())
+    })
+    .await;
+
+    run_with_service(|port| async move {
+        let client = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::LocalOnly)
+            .with_root_path(temp_dir_path)
+            .without_event_listener()
+            .authenticate(&mnemonic)
+            .await
+            .unwrap();
+
+        assert_eq!(client.account().id, account_id);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn fails_early_on_an_unwritable_root_path() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    // A path nested under a file can never be created
+    let file_path = temp_dir.path().join("file");
+    std::fs::write(&file_path, "").unwrap();
+    let root_path = file_path.join("root");
+
+    run_with_service(|port| async move {
+        let client = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(&root_path);
+
+        let status = client.validate_root_path().unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        assert!(status
+            .message()
+            .starts_with(&format!("Can't write to root path {}", root_path.display())));
+
+        let Err(AuthError::Transport(status)) = client.create_account("Test Client").await else {
+            panic!("creating an account with an unwritable root path succeeded");
+        };
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_logout_and_authenticate_again_on_the_same_connection() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (mnemonic, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+        let account_id = client.account().id.clone();
+
+        let client = client
+            .logout()
+            .await
+            .unwrap()
+            .authenticate(&mnemonic)
+            .await
+            .unwrap();
+
+        assert_eq!(client.account().id, account_id);
+        client.ping().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_shut_down_explicitly() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        client.shutdown().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_read_and_update_the_profile() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        assert_eq!(client.profile().await.unwrap().name(), "Test Client");
+
+        client.set_profile_name("Renamed Client").await.unwrap();
+        client.set_profile_icon("🦀").await.unwrap();
+
+        let profile = client.profile().await.unwrap();
+        assert_eq!(profile.name(), "Renamed Client");
+        assert_eq!(
+            profile.get_raw("iconEmoji"),
+            Some(RawValue::String("🦀".to_string()))
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_connect_over_a_prebuilt_channel() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let channel = tonic::transport::Endpoint::from_shared(format!("http://127.0.0.1:{port}"))
+            .unwrap()
+            .tcp_keepalive(Some(std::time::Duration::from_secs(30)))
+            .connect()
+            .await
+            .unwrap();
+
+        let (_, client) = AnytypeClient::from_channel(channel)
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        client.ping().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn can_authenticate_with_a_stored_token() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+        let token = client.token().to_string();
+
+        let reconnected = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .authenticate_with_token(&token)
+            .await
+            .unwrap();
+        assert_eq!(reconnected.account().id, client.account().id);
+        reconnected.ping().await.unwrap();
+
+        let result = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .authenticate_with_token("not a token")
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn failing_to_authenticate_with_a_token_stops_the_event_listener() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let result = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .authenticate_with_token("not a token")
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+
+        // A listener left running would keep resubscribing with the rejected token, warning
+        // about it after every backoff
+        let logs = capture_logs();
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        assert!(!logs.contains("anytype-heart event stream failed"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn requests_with_a_logged_out_token_fail_with_token_expired() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+        let token = client.token().to_string();
+
+        let reconnected = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .authenticate_with_token(&token)
+            .await
+            .unwrap();
+        reconnected.ping().await.unwrap();
+
+        client.logout().await.unwrap();
+
+        let status = reconnected.ping().await.unwrap_err();
+        assert!(TokenExpired::is_cause_of(&status));
+
+        let result = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .without_shutdown_on_drop()
+            .authenticate_with_token(&token)
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidToken)));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn authenticating_with_an_invalid_mnemonic_fails_with_a_typed_error() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let result = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .authenticate("definitely not a valid recovery phrase")
+            .await;
+
+        assert!(matches!(result, Err(AuthError::InvalidMnemonic)));
+    })
+    .await;
+}
+
+#[test]
+fn token_expired_is_told_apart_from_other_statuses() {
+    let status = tonic::Status::from(TokenExpired);
+    assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    assert!(TokenExpired::is_cause_of(&status));
+
+    assert!(!TokenExpired::is_cause_of(&tonic::Status::unauthenticated(
+        "some other reason"
+    )));
+    assert!(!TokenExpired::is_cause_of(&tonic::Status::internal(
+        "something broke"
+    )));
+}
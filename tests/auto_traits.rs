@@ -0,0 +1,14 @@
+//! Web servers share these across tasks and threads, so they have to stay `Send + Sync`
+
+use anytype_friend::{
+    AnytypeClient, AuthorizedAnytypeClient, Object, ObjectType, Relation, RelationValue, Space,
+};
+use static_assertions::assert_impl_all;
+
+assert_impl_all!(AnytypeClient: Send, Sync);
+assert_impl_all!(AuthorizedAnytypeClient: Send, Sync);
+assert_impl_all!(Space: Send, Sync);
+assert_impl_all!(Object: Send, Sync);
+assert_impl_all!(ObjectType: Send, Sync);
+assert_impl_all!(Relation: Send, Sync);
+assert_impl_all!(RelationValue: Send, Sync);
@@ -0,0 +1,108 @@
+mod utils;
+
+use std::collections::BTreeSet;
+
+use anytype_friend::{
+    AnytypeClient, ExportFormat, ImportFormat, ImportMode, NetworkSync, ObjectSpec, ObjectTypeSpec,
+};
+use utils::run_with_service;
+
+#[tokio::test]
+async fn export_can_export_an_object_and_a_space() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let archive = object.export(ExportFormat::Markdown).await.unwrap();
+        // Every zip archive starts with the local file header signature
+        assert!(archive.starts_with(b"PK"));
+
+        let archive = space.export_all(ExportFormat::Protobuf).await.unwrap();
+        assert!(archive.starts_with(b"PK"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn export_can_be_imported_back() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let archive = object.export(ExportFormat::Protobuf).await.unwrap();
+        let archive_path = temp_dir_path.join("export.zip");
+        std::fs::write(&archive_path, archive).unwrap();
+
+        let report = space
+            .import(&archive_path, ImportFormat::Protobuf)
+            .await
+            .unwrap();
+        assert!(report.objects_count >= 1);
+        assert!(!report.objects.is_empty());
+        assert!(report.skipped.is_none());
+
+        let report = space
+            .import_with_mode(
+                &archive_path,
+                ImportFormat::Protobuf,
+                ImportMode::IgnoreErrors,
+            )
+            .await
+            .unwrap();
+        assert!(report.objects_count >= 1);
+        assert!(report.skipped.is_none());
+    })
+    .await;
+}
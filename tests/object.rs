@@ -3,8 +3,9 @@ mod utils;
 use std::collections::{BTreeSet, HashMap};
 
 use anytype_friend::{
-    AnytypeClient, NetworkSync, ObjectDescription, ObjectSpec, ObjectTypeSpec, RelationFormat,
-    RelationSpec, RelationValue,
+    AnytypeClient, BundledType, ImportFormat, NetworkSync, ObjectDescription, ObjectEvent,
+    ObjectId, ObjectLayout, ObjectSpec, ObjectTypeId, ObjectTypeSpec, ParseObjectIdError, RawValue,
+    RelationDescription, RelationFormat, RelationId, RelationSpec, RelationValue,
 };
 use chrono::{DateTime, Utc};
 use utils::run_with_service;
@@ -47,18 +48,18 @@ async fn object_can_create_preexisting_one() {
                         format: RelationFormat::Url,
                     },
                 ]),
+                ..Default::default()
             })
             .await
             .unwrap();
 
         let object = space
             .create_object(ObjectDescription {
-                ty: object_type,
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     description_relation.clone(),
                     RelationValue::Text("We can create objects!".to_string()),
                 )]),
+                ..ObjectSpec::new(object_type, "Test Object").as_description()
             })
             .await
             .unwrap();
@@ -141,33 +142,33 @@ async fn object_can_create_one_with_all_basic_relation_formats() {
         let object_type = space
             .obtain_object_type(&ObjectTypeSpec {
                 name: "TestType".to_string(),
-                recommended_relations: BTreeSet::from([
-                    text_relation.as_spec(),
-                    number_relation.as_spec(),
-                    date_relation.as_spec(),
-                    checkbox_relation.as_spec(),
-                    url_relation.as_spec(),
-                    email_relation.as_spec(),
-                    phone_relation.as_spec(),
-                ]),
+                recommended_relations: [
+                    &text_relation,
+                    &number_relation,
+                    &date_relation,
+                    &checkbox_relation,
+                    &url_relation,
+                    &email_relation,
+                    &phone_relation,
+                ]
+                .into_iter()
+                .map(RelationSpec::from)
+                .collect(),
+                ..Default::default()
             })
             .await
             .unwrap();
 
-        let now = DateTime::from_timestamp(Utc::now().timestamp(), 0)
-            .unwrap()
-            .naive_utc();
+        let now = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
         let object = space
             .create_object(ObjectDescription {
-                ty: object_type,
-                name: "Test Object".to_string(),
                 relations: HashMap::from([
                     (
                         text_relation.clone(),
                         RelationValue::Text("text!".to_string()),
                     ),
                     (number_relation.clone(), RelationValue::Number(5.0)),
-                    (date_relation.clone(), RelationValue::Date(now)),
+                    (date_relation.clone(), RelationValue::DateUtc(now)),
                     (checkbox_relation.clone(), RelationValue::Checkbox(true)),
                     (
                         url_relation.clone(),
@@ -182,6 +183,7 @@ async fn object_can_create_one_with_all_basic_relation_formats() {
                         RelationValue::Phone("(555)555-5555".to_string()),
                     ),
                 ]),
+                ..ObjectSpec::new(object_type, "Test Object").as_description()
             })
             .await
             .unwrap();
@@ -197,7 +199,7 @@ async fn object_can_create_one_with_all_basic_relation_formats() {
         );
         assert_relations_eq!(
             object.get(&date_relation).await.unwrap(),
-            RelationValue::Date(now)
+            RelationValue::DateUtc(now)
         );
         assert_relations_eq!(
             object.get(&checkbox_relation).await.unwrap(),
@@ -254,18 +256,18 @@ async fn object_fails_to_create_with_incorrect_relation_format() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "TestType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
 
         let error = space
             .create_object(ObjectDescription {
-                ty: object_type.clone(),
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     number_relation.clone(),
                     RelationValue::Text("text!".to_string()),
                 )]),
+                ..ObjectSpec::new(object_type.clone(), "Test Object").as_description()
             })
             .await
             .unwrap_err();
@@ -277,12 +279,11 @@ async fn object_fails_to_create_with_incorrect_relation_format() {
 
         let error = space
             .create_object(ObjectDescription {
-                ty: object_type,
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     email_relation.clone(),
                     RelationValue::Phone("sneaky@email.com".to_string()),
                 )]),
+                ..ObjectSpec::new(object_type, "Test Object").as_description()
             })
             .await
             .unwrap_err();
@@ -315,6 +316,7 @@ async fn object_can_create_with_object_relations() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "ObjectTypeRelationTest".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -328,19 +330,11 @@ async fn object_can_create_with_object_relations() {
             .await
             .unwrap();
         let sample_object = space
-            .create_object(ObjectDescription {
-                ty: object_type.clone(),
-                name: "SampleObject".to_string(),
-                relations: HashMap::new(),
-            })
+            .create_object(ObjectSpec::new(object_type.clone(), "SampleObject").as_description())
             .await
             .unwrap();
         let sample_object_2 = space
-            .create_object(ObjectDescription {
-                ty: object_type,
-                name: "SampleObject 2".to_string(),
-                relations: HashMap::new(),
-            })
+            .create_object(ObjectSpec::new(object_type, "SampleObject 2").as_description())
             .await
             .unwrap();
 
@@ -348,18 +342,18 @@ async fn object_can_create_with_object_relations() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "TestType".to_string(),
                 recommended_relations: BTreeSet::from([relation.as_spec()]),
+                ..Default::default()
             })
             .await
             .unwrap();
 
         let object = space
             .create_object(ObjectDescription {
-                ty: object_type,
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     relation.clone(),
                     RelationValue::Object(vec![sample_object.clone(), sample_object_2.clone()]),
                 )]),
+                ..ObjectSpec::new(object_type, "Test Object").as_description()
             })
             .await
             .unwrap();
@@ -393,6 +387,7 @@ async fn object_fails_to_create_with_incorrect_object_type_relations() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "CorrectObjectType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -400,6 +395,7 @@ async fn object_fails_to_create_with_incorrect_object_type_relations() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "WrongObjectType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -413,19 +409,13 @@ async fn object_fails_to_create_with_incorrect_object_type_relations() {
             .await
             .unwrap();
         let correct_object = space
-            .create_object(ObjectDescription {
-                ty: correct_object_type.clone(),
-                name: "SampleObject".to_string(),
-                relations: HashMap::new(),
-            })
+            .create_object(
+                ObjectSpec::new(correct_object_type.clone(), "SampleObject").as_description(),
+            )
             .await
             .unwrap();
         let wrong_object = space
-            .create_object(ObjectDescription {
-                ty: wrong_object_type,
-                name: "SampleObject 2".to_string(),
-                relations: HashMap::new(),
-            })
+            .create_object(ObjectSpec::new(wrong_object_type, "SampleObject 2").as_description())
             .await
             .unwrap();
 
@@ -433,18 +423,18 @@ async fn object_fails_to_create_with_incorrect_object_type_relations() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "TestType".to_string(),
                 recommended_relations: BTreeSet::from([relation.as_spec()]),
+                ..Default::default()
             })
             .await
             .unwrap();
 
         let err = space
             .create_object(ObjectDescription {
-                ty: object_type,
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     relation.clone(),
                     RelationValue::Object(vec![correct_object.clone(), wrong_object.clone()]),
                 )]),
+                ..ObjectSpec::new(object_type, "Test Object").as_description()
             })
             .await
             .unwrap_err();
@@ -477,6 +467,7 @@ async fn relation_can_obtain_a_preexisting_one() {
             .create_object_type(&ObjectTypeSpec {
                 name: "TestObjectType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -486,11 +477,7 @@ async fn relation_can_obtain_a_preexisting_one() {
             ty: object_type.clone(),
         };
         let created_object = space
-            .create_object(ObjectDescription {
-                ty: spec.ty.clone(),
-                name: spec.name.clone(),
-                relations: HashMap::new(),
-            })
+            .create_object(ObjectSpec::new(spec.ty.clone(), spec.name.clone()).as_description())
             .await
             .unwrap();
         let object = match space.get_object(&spec).await.unwrap() {
@@ -500,6 +487,8 @@ async fn relation_can_obtain_a_preexisting_one() {
         assert_eq!(created_object.id(), object.id());
         assert_eq!(object.name(), "TestObject");
         assert_eq!(object.ty().await.unwrap().id(), object_type.id());
+        assert_eq!(object.type_name().await.unwrap(), "TestObjectType");
+        assert_eq!(object.layout(), ObjectLayout::Basic);
 
         let obtained_object = space.obtain_object(&spec).await.unwrap();
         assert_eq!(object.id(), obtained_object.id());
@@ -527,6 +516,7 @@ async fn relation_can_obtain_a_new_one() {
             .create_object_type(&ObjectTypeSpec {
                 name: "TestObjectType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -545,3 +535,1879 @@ async fn relation_can_obtain_a_new_one() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn object_can_list_all_objects_of_a_type() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .create_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let first = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type.clone(),
+                name: "First".to_string(),
+            })
+            .await
+            .unwrap();
+        let second = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type.clone(),
+                name: "Second".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let ids = space
+            .list_objects(&object_type)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(ids, BTreeSet::from([first.id(), second.id()]));
+        assert_eq!(space.count_objects(&object_type).await.unwrap(), 2);
+
+        let strict_ids = space
+            .list_objects_strict(&object_type)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(strict_ids, ids);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_list_objects_modified_since() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .create_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let before = Utc::now() - chrono::Duration::seconds(1);
+        let object = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let modified = space.objects_modified_since(before).await.unwrap();
+        assert!(modified.iter().any(|modified| modified.id() == object.id()));
+
+        let modified = space
+            .objects_modified_since(Utc::now() + chrono::Duration::days(1))
+            .await
+            .unwrap();
+        assert!(modified.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_change_its_type() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let description_relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Description".to_string(),
+                format: RelationFormat::Text,
+            })
+            .await
+            .unwrap();
+        let first_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "FirstType".to_string(),
+                recommended_relations: BTreeSet::from([description_relation.as_spec()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let second_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "SecondType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let object = space
+            .create_object(ObjectDescription {
+                relations: HashMap::from([(
+                    description_relation.clone(),
+                    RelationValue::Text("Stays after changing type".to_string()),
+                )]),
+                ..ObjectSpec::new(first_type, "TestObject").as_description()
+            })
+            .await
+            .unwrap();
+
+        let handle = object.clone();
+        object.set_type(&second_type).await.unwrap();
+        assert_eq!(object.ty().await.unwrap().id(), second_type.id());
+        assert_eq!(handle.ty().await.unwrap().id(), second_type.id());
+
+        let object = space
+            .get_object(&ObjectSpec {
+                ty: second_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_relations_eq!(
+            object.get(&description_relation).await.unwrap(),
+            RelationValue::Text("Stays after changing type".to_string())
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_get_many_by_id_in_order() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for name in ["A", "B", "C"] {
+            let object = space
+                .obtain_object(&ObjectSpec {
+                    ty: object_type.clone(),
+                    name: name.to_string(),
+                })
+                .await
+                .unwrap();
+            ids.push(object.id());
+        }
+
+        let requested = vec![ids[2], ids[0], ids[1]];
+        let objects = space.get_objects_by_id(requested.clone()).await.unwrap();
+        assert_eq!(
+            objects.iter().map(|object| object.id()).collect::<Vec<_>>(),
+            requested
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_create_a_bookmark_from_a_url() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        // Nothing listens on this port, so fetching the preview fails but the bookmark is still
+        // created
+        let created = space
+            .create_bookmark("http://127.0.0.1:1/unreachable")
+            .await
+            .unwrap();
+        assert!(created.preview_error.is_some());
+        let bookmark = created.object;
+        assert_eq!(bookmark.layout(), ObjectLayout::Bookmark);
+
+        let typed = bookmark.as_bookmark().unwrap();
+        assert_eq!(typed.object().id(), bookmark.id());
+        assert_eq!(
+            typed.url().as_deref(),
+            Some("http://127.0.0.1:1/unreachable")
+        );
+        assert_eq!(typed.description(), None);
+
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let page = space
+            .create_object(ObjectSpec::new(page_type, "Not A Bookmark").as_description())
+            .await
+            .unwrap();
+        assert!(page.as_bookmark().is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_create_one_with_description_icon_and_details() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let description_relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Description".to_string(),
+                format: RelationFormat::Text,
+            })
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::from([description_relation.as_spec()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let object = space
+            .create_object(ObjectDescription {
+                description: Some("Set at creation".to_string()),
+                icon: Some("📚".to_string()),
+                details: HashMap::from([("layoutAlign".to_string(), RawValue::Number(1.0))]),
+                ..ObjectSpec::new(object_type, "TestObject").as_description()
+            })
+            .await
+            .unwrap();
+
+        assert_relations_eq!(
+            object.get(&description_relation).await.unwrap(),
+            RelationValue::Text("Set at creation".to_string())
+        );
+        assert_eq!(object.get_raw("layoutAlign"), Some(RawValue::Number(1.0)));
+        assert_eq!(
+            object.get_raw("iconEmoji"),
+            Some(RawValue::String("📚".to_string()))
+        );
+        assert_eq!(object.get_raw("notARelationKey"), None);
+        assert_eq!(object.file_url(), None);
+
+        let details = object.raw_details();
+        assert_eq!(details.get("layoutAlign"), Some(&RawValue::Number(1.0)));
+        assert!(!details.contains_key("notARelationKey"));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_clear_a_relation() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let date_relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Date Relation Test".to_string(),
+                format: RelationFormat::Date,
+            })
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::from([date_relation.as_spec()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let now = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+        let object = space
+            .create_object(ObjectDescription {
+                relations: HashMap::from([(date_relation.clone(), RelationValue::DateUtc(now))]),
+                ..ObjectSpec::new(object_type.clone(), "TestObject").as_description()
+            })
+            .await
+            .unwrap();
+
+        let previous = object.clear(&date_relation).await.unwrap().unwrap();
+        assert_relations_eq!(previous, RelationValue::DateUtc(now));
+        assert!(object.get(&date_relation).await.is_none());
+
+        let object = space
+            .get_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(object.get(&date_relation).await.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_fails_to_set_non_finite_numbers() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let number_relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Number Relation Test".to_string(),
+                format: RelationFormat::Number,
+            })
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::from([number_relation.as_spec()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap();
+
+        for number in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let error = object
+                .set(&number_relation, RelationValue::Number(number))
+                .await
+                .unwrap_err();
+            assert!(error
+                .message()
+                .starts_with("Expected a finite number but received"));
+        }
+
+        let handle = object.clone();
+        object
+            .set(&number_relation, RelationValue::Number(42.0))
+            .await
+            .unwrap();
+        assert_relations_eq!(
+            handle.get(&number_relation).await.unwrap(),
+            RelationValue::Number(42.0)
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_list_its_relation_keys() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::new(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let object = space
+            .create_object(ObjectDescription {
+                description: Some("Has a description".to_string()),
+                icon: Some("📚".to_string()),
+                ..ObjectSpec::new(object_type, "TestObject").as_description()
+            })
+            .await
+            .unwrap();
+
+        let keys = object.relation_keys();
+        assert!(keys.contains(&"description".to_string()));
+        assert!(keys.contains(&"iconEmoji".to_string()));
+        assert!(!keys.contains(&"spaceId".to_string()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_watch_its_relations_change() {
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let text_relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Text Relation Test".to_string(),
+                format: RelationFormat::Text,
+            })
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec {
+                name: "TestObjectType".to_string(),
+                recommended_relations: BTreeSet::from([text_relation.as_spec()]),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec {
+                ty: object_type,
+                name: "TestObject".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let mut changes = object.watch().await.unwrap();
+
+        object
+            .set(&text_relation, RelationValue::Text("Hello".to_string()))
+            .await
+            .unwrap();
+        let (relation, value) = tokio::time::timeout(Duration::from_secs(5), changes.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(relation.id(), text_relation.id());
+        assert_relations_eq!(value.unwrap(), RelationValue::Text("Hello".to_string()));
+
+        object.clear(&text_relation).await.unwrap();
+        let (relation, value) = tokio::time::timeout(Duration::from_secs(5), changes.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(relation.id(), text_relation.id());
+        assert!(value.is_none());
+
+        changes.cancel().await.unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_get_one_by_a_unique_relation() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let external_id = space
+            .obtain_relation(&RelationSpec::text("External Id"))
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "TestObjectType",
+                [external_id.as_spec()],
+            ))
+            .await
+            .unwrap();
+
+        let mut ids = Vec::new();
+        for id in ["first", "second", "second"] {
+            let object = space
+                .create_object(ObjectDescription {
+                    relations: HashMap::from([(
+                        external_id.clone(),
+                        RelationValue::Text(id.to_string()),
+                    )]),
+                    ..ObjectSpec::new(object_type.clone(), "Same Name").as_description()
+                })
+                .await
+                .unwrap();
+            ids.push(object.id());
+        }
+
+        let object = space
+            .get_object_by_relation(&external_id, RelationValue::Text("first".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(object.id(), ids[0]);
+
+        assert!(space
+            .get_object_by_relation(&external_id, RelationValue::Text("missing".to_string()))
+            .await
+            .unwrap()
+            .is_none());
+
+        let error = space
+            .get_object_by_relation(&external_id, RelationValue::Text("second".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "More than one object with the same value for relation External Id"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_resolve_a_relation_with_hundreds_of_objects() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let item_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Item", []))
+            .await
+            .unwrap();
+        let items_relation = space
+            .obtain_relation(&RelationSpec::object("Items", [item_type.id()]))
+            .await
+            .unwrap();
+        let holder_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Holder", [items_relation.as_spec()]))
+            .await
+            .unwrap();
+
+        let mut items = Vec::new();
+        for index in 0..450 {
+            let item = space
+                .create_object(
+                    ObjectSpec::new(item_type.clone(), format!("Item {index}")).as_description(),
+                )
+                .await
+                .unwrap();
+            items.push(item);
+        }
+        let ids = items.iter().map(|item| item.id()).collect::<Vec<_>>();
+
+        let holder = space
+            .create_object(ObjectDescription {
+                relations: HashMap::from([(items_relation.clone(), RelationValue::Object(items))]),
+                ..ObjectSpec::new(holder_type, "Holder").as_description()
+            })
+            .await
+            .unwrap();
+
+        let Some(RelationValue::Object(resolved)) = holder.get(&items_relation).await else {
+            panic!("Items relation didn't hold objects");
+        };
+        assert_eq!(
+            resolved.iter().map(|item| item.id()).collect::<Vec<_>>(),
+            ids
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_fails_to_set_malformed_values_when_checking_them() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .with_well_formed_values()
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let url_relation = space
+            .obtain_relation(&RelationSpec::url("Url Relation Test"))
+            .await
+            .unwrap();
+        let email_relation = space
+            .obtain_relation(&RelationSpec::email("Email Relation Test"))
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "TestObjectType",
+                [url_relation.as_spec(), email_relation.as_spec()],
+            ))
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type, "TestObject"))
+            .await
+            .unwrap();
+
+        let error = object
+            .set(&url_relation, RelationValue::Url("not a url".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.message(), "`not a url` is not a well formed Url");
+
+        let error = object
+            .set(&email_relation, RelationValue::Email("nobody".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.message(), "`nobody` is not a well formed Email");
+
+        object
+            .set(
+                &url_relation,
+                RelationValue::Url("https://anytype.io".to_string()),
+            )
+            .await
+            .unwrap();
+        object
+            .set(
+                &email_relation,
+                RelationValue::Email("someone@example.com".to_string()),
+            )
+            .await
+            .unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_list_its_history() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let text_relation = space
+            .obtain_relation(&RelationSpec::text("Text Relation Test"))
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "TestObjectType",
+                [text_relation.as_spec()],
+            ))
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type, "TestObject"))
+            .await
+            .unwrap();
+        object
+            .set(&text_relation, RelationValue::Text("Hello".to_string()))
+            .await
+            .unwrap();
+
+        let history = object.history().await.unwrap();
+        assert!(!history.is_empty());
+
+        let latest = object.at_version(&history[0]).await.unwrap();
+        assert_eq!(latest.id(), object.id());
+        assert_eq!(latest.name(), "TestObject");
+        assert_eq!(latest.version(), &history[0]);
+
+        // Versions are identified by their last change, so one listed before a later change
+        // keeps showing the object as it was then
+        object
+            .set(&text_relation, RelationValue::Text("Goodbye".to_string()))
+            .await
+            .unwrap();
+        let past = object.at_version(&history[0]).await.unwrap();
+        assert_relations_eq!(
+            past.get(&text_relation).await.unwrap(),
+            RelationValue::Text("Hello".to_string())
+        );
+        assert_relations_eq!(
+            object.get(&text_relation).await.unwrap(),
+            RelationValue::Text("Goodbye".to_string())
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_obtain_many_at_once() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        let other_object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("OtherObjectType", []))
+            .await
+            .unwrap();
+        let existing = space
+            .obtain_object(&ObjectSpec::new(object_type.clone(), "Existing"))
+            .await
+            .unwrap();
+
+        let objects = space
+            .obtain_objects(vec![
+                ObjectSpec::new(object_type.clone(), "New"),
+                ObjectSpec::new(object_type.clone(), "Existing"),
+                ObjectSpec::new(other_object_type.clone(), "Existing"),
+                ObjectSpec::new(object_type.clone(), "New"),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(objects.len(), 4);
+        assert_eq!(objects[0].name(), "New");
+        assert_eq!(objects[1].id(), existing.id());
+        assert_ne!(objects[2].id(), existing.id());
+        assert_eq!(objects[2].ty().await.unwrap().id(), other_object_type.id());
+        assert_eq!(objects[3].id(), objects[0].id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_find_all_duplicates() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        let spec = ObjectSpec::new(object_type, "Quick Capture");
+        assert!(space.find_objects(&spec).await.unwrap().is_empty());
+
+        let first = space.create_object(spec.as_description()).await.unwrap();
+        let second = space.create_object(spec.as_description()).await.unwrap();
+
+        let found = space
+            .find_objects(&spec)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(found, BTreeSet::from([first.id(), second.id()]));
+        assert!(space.get_object(&spec).await.is_err());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_set_a_raw_detail() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type.clone(), "TestObject"))
+            .await
+            .unwrap();
+
+        object
+            .set_raw_detail("layoutAlign", RawValue::Number(2.0))
+            .await
+            .unwrap();
+        assert_eq!(object.get_raw("layoutAlign"), Some(RawValue::Number(2.0)));
+
+        let refetched = space
+            .get_object(&ObjectSpec::new(object_type, "TestObject"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            refetched.get_raw("layoutAlign"),
+            Some(RawValue::Number(2.0))
+        );
+
+        let error = object
+            .set_raw_detail("id", RawValue::String("bafy".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_listing_can_be_cancelled() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        space
+            .obtain_object(&ObjectSpec::new(object_type.clone(), "TestObject"))
+            .await
+            .unwrap();
+
+        let error = space
+            .list_objects_cancellable(&object_type, std::future::ready(()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Cancelled);
+
+        let objects = space
+            .list_objects_cancellable(&object_type, std::future::pending())
+            .await
+            .unwrap();
+        assert_eq!(objects.len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_keeps_the_order_of_object_relation_values() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let item_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Item", []))
+            .await
+            .unwrap();
+        let items_relation = space
+            .obtain_relation(&RelationSpec::object("Ordered Items", [item_type.id()]))
+            .await
+            .unwrap();
+        let holder_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Holder", [items_relation.as_spec()]))
+            .await
+            .unwrap();
+
+        let a = space
+            .obtain_object(&ObjectSpec::new(item_type.clone(), "A"))
+            .await
+            .unwrap();
+        let b = space
+            .obtain_object(&ObjectSpec::new(item_type.clone(), "B"))
+            .await
+            .unwrap();
+        let c = space
+            .obtain_object(&ObjectSpec::new(item_type, "C"))
+            .await
+            .unwrap();
+        let holder = space
+            .obtain_object(&ObjectSpec::new(holder_type, "Holder"))
+            .await
+            .unwrap();
+
+        // Neither creation order nor id order
+        for order in [vec![c.clone(), a.clone(), b.clone()], vec![b, c, a]] {
+            let ids = order.iter().map(|object| object.id()).collect::<Vec<_>>();
+            holder
+                .set(&items_relation, RelationValue::Object(order))
+                .await
+                .unwrap();
+
+            let Some(RelationValue::Object(resolved)) = holder.get(&items_relation).await else {
+                panic!("Ordered Items relation didn't hold objects");
+            };
+            assert_eq!(
+                resolved
+                    .iter()
+                    .map(|object| object.id())
+                    .collect::<Vec<_>>(),
+                ids
+            );
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_fails_to_create_with_more_values_than_max_count() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let author_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Author", []))
+            .await
+            .unwrap();
+        let relation = space
+            .create_relation(RelationDescription {
+                max_count: Some(1),
+                ..RelationSpec::object("Primary Author", [author_type.id()]).as_description()
+            })
+            .await
+            .unwrap();
+        let book_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Book", [relation.as_spec()]))
+            .await
+            .unwrap();
+
+        let first = space
+            .obtain_object(&ObjectSpec::new(author_type.clone(), "First Author"))
+            .await
+            .unwrap();
+        let second = space
+            .obtain_object(&ObjectSpec::new(author_type, "Second Author"))
+            .await
+            .unwrap();
+
+        let mut description = ObjectSpec::new(book_type.clone(), "Book").as_description();
+        description.relations = HashMap::from([(
+            relation.clone(),
+            RelationValue::Object(vec![first.clone(), second]),
+        )]);
+        let error = space.create_object(description).await.unwrap_err();
+        assert_eq!(error.message(), "Expected at most 1 values but received 2");
+
+        let book = space
+            .obtain_object(&ObjectSpec::new(book_type, "Book"))
+            .await
+            .unwrap();
+        book.set(&relation, RelationValue::Object(vec![first]))
+            .await
+            .unwrap();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_be_marked_as_favorite() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type, "TestObject"))
+            .await
+            .unwrap();
+        assert!(!object.is_favorite());
+
+        object.set_favorite(true).await.unwrap();
+        assert!(object.is_favorite());
+        let favorites = space.list_favorite_objects().await.unwrap();
+        assert!(favorites
+            .iter()
+            .any(|favorite| favorite.id() == object.id()));
+
+        object.set_favorite(false).await.unwrap();
+        assert!(!object.is_favorite());
+        let favorites = space.list_favorite_objects().await.unwrap();
+        assert!(!favorites
+            .iter()
+            .any(|favorite| favorite.id() == object.id()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_create_with_key_can_be_retried() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let external_id = space
+            .obtain_relation(&RelationSpec::text("Import Id"))
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Imported", [external_id.as_spec()]))
+            .await
+            .unwrap();
+
+        let description = || {
+            let mut description =
+                ObjectSpec::new(object_type.clone(), "Imported Object").as_description();
+            description.relations = HashMap::from([(
+                external_id.clone(),
+                RelationValue::Text("row-1".to_string()),
+            )]);
+            description
+        };
+
+        let (created, existence) = space
+            .create_object_with_key(description(), &external_id)
+            .await
+            .unwrap();
+        assert!(existence.is_created());
+
+        let (retried, existence) = space
+            .create_object_with_key(description(), &external_id)
+            .await
+            .unwrap();
+        assert!(!existence.is_created());
+        assert_eq!(retried.id(), created.id());
+
+        let error = space
+            .create_object_with_key(
+                ObjectSpec::new(object_type, "No Key").as_description(),
+                &external_id,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_can_list_objects_of_several_layouts() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let page = space
+            .create_object(ObjectSpec::new(page_type, "TestPage").as_description())
+            .await
+            .unwrap();
+        assert_eq!(page.layout(), ObjectLayout::Basic);
+        let bookmark = space
+            .create_bookmark("http://127.0.0.1:1/unreachable")
+            .await
+            .unwrap()
+            .object;
+
+        let ids = space
+            .list_objects_with_layouts(&[ObjectLayout::Basic, ObjectLayout::Bookmark])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.id())
+            .collect::<BTreeSet<_>>();
+        assert!(ids.contains(&page.id()));
+        assert!(ids.contains(&bookmark.id()));
+
+        let ids = space
+            .list_objects_with_layouts(&[ObjectLayout::Bookmark])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.id())
+            .collect::<BTreeSet<_>>();
+        assert!(!ids.contains(&page.id()));
+        assert!(ids.contains(&bookmark.id()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_has_a_file_url_only_when_listed_by_its_layout() {
+    // The smallest valid PNG, a single transparent pixel
+    const PIXEL_PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+        0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0A, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+
+        // Importing a note that embeds a local image makes anytype-heart create a file object
+        // for the image
+        let markdown_path = temp_dir_path.join("markdown");
+        std::fs::create_dir(&markdown_path).unwrap();
+        std::fs::write(markdown_path.join("pixel.png"), PIXEL_PNG).unwrap();
+        std::fs::write(
+            markdown_path.join("Note.md"),
+            "# Note\n\n![Pixel](pixel.png)\n",
+        )
+        .unwrap();
+        space
+            .import(&markdown_path, ImportFormat::Markdown)
+            .await
+            .unwrap();
+
+        let images = space
+            .list_objects_with_layouts(&[ObjectLayout::Image, ObjectLayout::File])
+            .await
+            .unwrap();
+        let image = images.first().unwrap();
+        let file_url = image.file_url().unwrap();
+        assert!(file_url.starts_with(&format!("{}/file/", space.info().gateway_url())));
+
+        // File objects stay out of the usual searches unless their layout is asked for
+        assert!(space
+            .get_objects_by_id([image.id()])
+            .await
+            .unwrap()
+            .is_empty());
+    })
+    .await;
+}
+
+#[test]
+fn object_id_can_be_parsed_from_a_string() {
+    let id = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    assert_eq!(id.parse::<ObjectId>().unwrap().to_string(), id);
+    assert_eq!(id.parse::<ObjectTypeId>().unwrap().to_string(), id);
+    assert_eq!(id.parse::<RelationId>().unwrap().to_string(), id);
+
+    assert_eq!(
+        "not an id".parse::<ObjectId>(),
+        Err(ParseObjectIdError("not an id".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn object_can_be_fetched_by_a_stored_id() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type, "TestObject"))
+            .await
+            .unwrap();
+
+        let stored = object.id().to_string();
+        let objects = space
+            .get_objects_by_id([stored.parse().unwrap()])
+            .await
+            .unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].id(), object.id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn quick_create_obtains_the_type_and_creates_the_object() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let pages = RelationSpec {
+            name: "Pages".to_string(),
+            format: RelationFormat::Number,
+        };
+        let author = RelationSpec {
+            name: "Author".to_string(),
+            format: RelationFormat::Text,
+        };
+
+        let object = space
+            .quick_create(
+                "Book",
+                HashMap::from([
+                    (pages.clone(), RelationValue::Number(320.0)),
+                    (author.clone(), RelationValue::Text("Ursula".to_string())),
+                ]),
+                "The Dispossessed",
+            )
+            .await
+            .unwrap();
+        assert_eq!(object.name(), "The Dispossessed");
+        assert_eq!(object.type_name().await.unwrap(), "Book");
+
+        let object_type = space
+            .get_object_type(&ObjectTypeSpec::new(
+                "Book",
+                [pages.clone(), author.clone()],
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        let pages = space.get_relation(&pages).await.unwrap().unwrap();
+        let author = space.get_relation(&author).await.unwrap().unwrap();
+        assert_relations_eq!(
+            object.get(&pages).await.unwrap(),
+            RelationValue::Number(320.0)
+        );
+        assert_relations_eq!(
+            object.get(&author).await.unwrap(),
+            RelationValue::Text("Ursula".to_string())
+        );
+
+        let second = space
+            .quick_create(
+                "Book",
+                HashMap::from([
+                    (pages.as_spec(), RelationValue::Number(416.0)),
+                    (author.as_spec(), RelationValue::Text("Ursula".to_string())),
+                ]),
+                "The Left Hand of Darkness",
+            )
+            .await
+            .unwrap();
+        assert_ne!(second.id(), object.id());
+        assert_eq!(second.ty().await.unwrap().id(), object_type.id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_can_read_values_of_many_objects_at_once() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let author_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Author", []))
+            .await
+            .unwrap();
+        let authors = space
+            .obtain_relation(&RelationSpec {
+                name: "Authors".to_string(),
+                format: RelationFormat::Object {
+                    types: BTreeSet::from([author_type.id()]),
+                },
+            })
+            .await
+            .unwrap();
+        let pages = space
+            .obtain_relation(&RelationSpec {
+                name: "Pages".to_string(),
+                format: RelationFormat::Number,
+            })
+            .await
+            .unwrap();
+        let book_type = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "Book",
+                [authors.as_spec(), pages.as_spec()],
+            ))
+            .await
+            .unwrap();
+
+        let first = space
+            .obtain_object(&ObjectSpec::new(author_type.clone(), "First Author"))
+            .await
+            .unwrap();
+        let second = space
+            .obtain_object(&ObjectSpec::new(author_type, "Second Author"))
+            .await
+            .unwrap();
+
+        let mut description = ObjectSpec::new(book_type.clone(), "Co-written").as_description();
+        description.relations = HashMap::from([
+            (
+                authors.clone(),
+                RelationValue::Object(vec![second.clone(), first.clone()]),
+            ),
+            (pages.clone(), RelationValue::Number(120.0)),
+        ]);
+        let co_written = space.create_object(description).await.unwrap();
+
+        let mut description = ObjectSpec::new(book_type, "Solo").as_description();
+        description.relations =
+            HashMap::from([(authors.clone(), RelationValue::Object(vec![first.clone()]))]);
+        let solo = space.create_object(description).await.unwrap();
+
+        let values = space
+            .get_values(&[co_written, solo], &[authors.clone(), pages.clone()])
+            .await
+            .unwrap();
+        assert_eq!(values.len(), 2);
+
+        assert_eq!(values[0].len(), 2);
+        assert_relations_eq!(
+            values[0][&authors].clone(),
+            RelationValue::Object(vec![second, first.clone()])
+        );
+        assert_relations_eq!(values[0][&pages].clone(), RelationValue::Number(120.0));
+
+        assert_eq!(values[1].len(), 1);
+        assert_relations_eq!(
+            values[1][&authors].clone(),
+            RelationValue::Object(vec![first])
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_fails_to_read_values_of_unsupported_formats() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let select_relation = space
+            .obtain_relation(&RelationSpec::select("Select Values Test"))
+            .await
+            .unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let object = space
+            .create_object(ObjectSpec::new(page_type, "Selecting").as_description())
+            .await
+            .unwrap();
+
+        let error = space
+            .get_values(&[object.clone()], &[select_relation.clone()])
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::Unimplemented);
+        assert!(object.get(&select_relation).await.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_description_can_be_read_and_set() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let object = space
+            .create_object(ObjectSpec::new(page_type, "Described").as_description())
+            .await
+            .unwrap();
+        assert_eq!(object.description().await.unwrap(), None);
+
+        let previous = object.set_description("First").await.unwrap();
+        assert_eq!(previous, None);
+        assert_eq!(
+            object.description().await.unwrap().as_deref(),
+            Some("First")
+        );
+
+        let previous = object.set_description("Second").await.unwrap();
+        assert_eq!(previous.as_deref(), Some("First"));
+        assert_eq!(
+            object.description().await.unwrap().as_deref(),
+            Some("Second")
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_serializes_concurrent_sets() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let object = space
+            .create_object(ObjectSpec::new(page_type, "Contended").as_description())
+            .await
+            .unwrap();
+
+        let (first, second, third) = (object.clone(), object.clone(), object.clone());
+        let (a, b, c) = tokio::join!(
+            first.set_description("A"),
+            second.set_description("B"),
+            third.set_description("C"),
+        );
+        let previous = [a.unwrap(), b.unwrap(), c.unwrap()];
+        let last = object.description().await.unwrap().unwrap();
+
+        // Each set saw the value written by the one before it, so following previous values
+        // back from the final one walks every set exactly once
+        let mut written = BTreeSet::from(["A", "B", "C"].map(String::from));
+        let mut current = Some(last);
+        for _ in 0..3 {
+            let value = current.take().unwrap();
+            assert!(written.remove(&value));
+            let index = ["A", "B", "C"].iter().position(|v| **v == value).unwrap();
+            current = previous[index].clone();
+        }
+        assert_eq!(current, None);
+        assert!(written.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_reads_mixed_lists_leniently() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let relation = space
+            .obtain_relation(&RelationSpec::object("MixedLinks", []))
+            .await
+            .unwrap();
+        let linked = space
+            .create_object(ObjectSpec::new(page_type.clone(), "Linked").as_description())
+            .await
+            .unwrap();
+        let object = space
+            .create_object(ObjectSpec::new(page_type, "Imported").as_description())
+            .await
+            .unwrap();
+
+        let keys_before = object.relation_keys();
+        object
+            .set(&relation, RelationValue::Object(vec![linked.clone()]))
+            .await
+            .unwrap();
+        let key = object
+            .relation_keys()
+            .into_iter()
+            .find(|key| !keys_before.contains(key))
+            .unwrap();
+
+        // Imported data can hold lists whose values don't all have the same kind
+        let mixed = vec![
+            RawValue::String(linked.id().to_string()),
+            RawValue::Number(7.0),
+        ];
+        object
+            .set_raw_detail(&key, RawValue::List(mixed.clone()))
+            .await
+            .unwrap();
+
+        assert_eq!(object.get_raw_list(&key), Some(mixed));
+        assert_relations_eq!(
+            object.get(&relation).await.unwrap(),
+            RelationValue::Object(vec![linked])
+        );
+        assert_eq!(object.get_raw_list("notARelationKey"), None);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_subscription_starts_with_a_snapshot() {
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Subscribed", []))
+            .await
+            .unwrap();
+        let existing = space
+            .create_object(ObjectSpec::new(object_type.clone(), "Existing").as_description())
+            .await
+            .unwrap();
+
+        let events = space.subscribe_objects(&object_type).await.unwrap();
+        let mut events = std::pin::pin!(events);
+
+        let Some(ObjectEvent::Snapshot(snapshot)) =
+            tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .unwrap()
+        else {
+            panic!("subscription didn't start with a snapshot");
+        };
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].id(), existing.id());
+
+        let added = space
+            .create_object(ObjectSpec::new(object_type, "Added").as_description())
+            .await
+            .unwrap();
+        let Some(ObjectEvent::Add(object)) =
+            tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .unwrap()
+        else {
+            panic!("creating an object of the type wasn't reported as an add");
+        };
+        assert_eq!(object.id(), added.id());
+        assert_eq!(object.name(), "Added");
+
+        existing
+            .set_raw_detail("name", RawValue::String("Renamed".to_string()))
+            .await
+            .unwrap();
+        let Some(ObjectEvent::Change(object)) =
+            tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .unwrap()
+        else {
+            panic!("renaming an object of the type wasn't reported as a change");
+        };
+        assert_eq!(object.id(), existing.id());
+        assert_eq!(object.name(), "Renamed");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_subscription_takes_a_new_snapshot_after_falling_behind() {
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Flooded", []))
+            .await
+            .unwrap();
+
+        let events = space.subscribe_objects(&object_type).await.unwrap();
+        let mut events = std::pin::pin!(events);
+        let Some(ObjectEvent::Snapshot(snapshot)) =
+            tokio::time::timeout(Duration::from_secs(5), events.next())
+                .await
+                .unwrap()
+        else {
+            panic!("subscription didn't start with a snapshot");
+        };
+        assert!(snapshot.is_empty());
+
+        // More objects than the client buffers events for, without reading any of them
+        let created = space
+            .obtain_objects(
+                (0..300)
+                    .map(|index| ObjectSpec::new(object_type.clone(), format!("Flood {index}")))
+                    .collect(),
+            )
+            .await
+            .unwrap();
+
+        let Some(ObjectEvent::Snapshot(snapshot)) =
+            tokio::time::timeout(Duration::from_secs(30), events.next())
+                .await
+                .unwrap()
+        else {
+            panic!("subscription didn't start over after falling behind");
+        };
+        assert_eq!(
+            snapshot
+                .iter()
+                .map(|object| object.id())
+                .collect::<BTreeSet<_>>(),
+            created
+                .iter()
+                .map(|object| object.id())
+                .collect::<BTreeSet<_>>()
+        );
+    })
+    .await;
+}
@@ -1,8 +1,12 @@
 mod utils;
 
 use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
 
-use anytype_friend::{AnytypeClient, NetworkSync, ObjectTypeSpec, RelationFormat, RelationSpec};
+use anytype_friend::{
+    AnytypeClient, BundledType, Existence, NetworkSync, ObjectSpec, ObjectTypeSpec,
+    RecommendedRelationsMismatch, RelationFormat, RelationGroup, RelationSpec, RelationValue,
+};
 use utils::run_with_service;
 
 #[tokio::test]
@@ -37,6 +41,7 @@ async fn object_type_can_obtain_a_preexisting_one_without_relations() {
                     format: RelationFormat::Url,
                 },
             ]),
+            ..Default::default()
         };
 
         let object_type = match space.get_object_type(&spec).await.unwrap() {
@@ -47,7 +52,11 @@ async fn object_type_can_obtain_a_preexisting_one_without_relations() {
         object_type
             .recommended_relations()
             .iter()
-            .for_each(|relation| assert!(spec.recommended_relations.contains(&relation.as_spec())));
+            .for_each(|relation| {
+                assert!(spec
+                    .recommended_relations
+                    .contains(&RelationSpec::from(relation)))
+            });
 
         let obtained_object_type = space.obtain_object_type(&spec).await.unwrap();
         assert_eq!(object_type.id(), obtained_object_type.id());
@@ -102,6 +111,7 @@ async fn object_type_can_obtain_a_preexisting_one_with_relations() {
                 description_relation.into_spec(),
                 source_relation.into_spec(),
             ]),
+            ..Default::default()
         };
 
         let object_type = match space.get_object_type(&spec).await.unwrap() {
@@ -112,7 +122,11 @@ async fn object_type_can_obtain_a_preexisting_one_with_relations() {
         object_type
             .recommended_relations()
             .iter()
-            .for_each(|relation| assert!(spec.recommended_relations.contains(&relation.as_spec())));
+            .for_each(|relation| {
+                assert!(spec
+                    .recommended_relations
+                    .contains(&RelationSpec::from(relation)))
+            });
 
         let obtained_object_type = space.obtain_object_type(&spec).await.unwrap();
         assert_eq!(object_type.id(), obtained_object_type.id());
@@ -143,12 +157,20 @@ async fn object_type_fails_to_obtain_on_unmatched_recommended_relations() {
                     format: RelationFormat::MultiSelect,
                 },
             ]),
+            ..Default::default()
         }).await.unwrap_err();
 
-        // TODO: This should be an enum variant of an error type we control instead of a string
         if !result.message().contains("ObjectType `Bookmark` exists but has different recommended relations from requested recommended relations") {
           panic!("Unexpected error on obtaining object {} {}", result.code(), result.message());
         }
+
+        let mismatch = RecommendedRelationsMismatch::from_status(&result).unwrap();
+        assert_eq!(mismatch.object_type, "Bookmark");
+        assert_eq!(
+            mismatch.requested,
+            BTreeSet::from([RelationSpec::multi_select("Tag")])
+        );
+        assert_ne!(mismatch.received, mismatch.requested);
     })
     .await;
 }
@@ -176,6 +198,7 @@ async fn object_type_can_obtain_a_new_one_with_preexisting_relations() {
                 name: "Tag".to_string(),
                 format: RelationFormat::MultiSelect,
             }]),
+            ..Default::default()
         };
 
         if space.get_object_type(&spec).await.unwrap().is_some() {
@@ -187,7 +210,11 @@ async fn object_type_can_obtain_a_new_one_with_preexisting_relations() {
         object_type
             .recommended_relations()
             .iter()
-            .for_each(|relation| assert!(spec.recommended_relations.contains(&relation.as_spec())));
+            .for_each(|relation| {
+                assert!(spec
+                    .recommended_relations
+                    .contains(&RelationSpec::from(relation)))
+            });
     })
     .await;
 }
@@ -216,6 +243,7 @@ async fn object_type_can_obtain_a_new_one_with_new_relations() {
         let spec = ObjectTypeSpec {
             name: "NewType".to_string(),
             recommended_relations: BTreeSet::from([relation_spec.clone()]),
+            ..Default::default()
         };
 
         if space.get_object_type(&spec).await.unwrap().is_some() {
@@ -227,7 +255,15 @@ async fn object_type_can_obtain_a_new_one_with_new_relations() {
         object_type
             .recommended_relations()
             .iter()
-            .for_each(|relation| assert!(spec.recommended_relations.contains(&relation.as_spec())));
+            .for_each(|relation| {
+                assert!(spec
+                    .recommended_relations
+                    .contains(&RelationSpec::from(relation)))
+            });
+
+        let round_trip = ObjectTypeSpec::from(&object_type);
+        assert_eq!(round_trip.name, spec.name);
+        assert_eq!(round_trip.recommended_relations, spec.recommended_relations);
 
         let relation = space.get_relation(&relation_spec).await.unwrap();
         match relation {
@@ -240,3 +276,550 @@ async fn object_type_can_obtain_a_new_one_with_new_relations() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn object_type_can_plan_obtaining_without_creating() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+
+        let relation_spec = RelationSpec {
+            name: "NewRelation".to_string(),
+            format: RelationFormat::Text,
+        };
+        let spec = ObjectTypeSpec {
+            name: "NewType".to_string(),
+            recommended_relations: BTreeSet::from([relation_spec.clone()]),
+            ..Default::default()
+        };
+
+        let plan = space.plan_obtain_object_type(&spec).await.unwrap();
+        assert_eq!(plan.relations_to_create, vec![relation_spec.clone()]);
+        assert_eq!(plan.object_types_to_create, vec!["NewType".to_string()]);
+        assert!(plan.objects_to_create.is_empty());
+
+        assert!(space.get_relation(&relation_spec).await.unwrap().is_none());
+        assert!(space.get_object_type(&spec).await.unwrap().is_none());
+
+        space.obtain_object_type(&spec).await.unwrap();
+        assert!(space
+            .plan_obtain_object_type(&spec)
+            .await
+            .unwrap()
+            .is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_can_get_all_with_the_same_name() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let spec = ObjectTypeSpec {
+            name: "Duplicated".to_string(),
+            recommended_relations: BTreeSet::new(),
+            ..Default::default()
+        };
+        let first = space.create_object_type(&spec).await.unwrap();
+        let second = space.create_object_type(&spec).await.unwrap();
+
+        let error = space.get_object_type(&spec).await.unwrap_err();
+        assert_eq!(
+            error.message(),
+            "More than one object type with same name Duplicated"
+        );
+
+        let ids = space
+            .get_object_types_by_name("Duplicated")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object_type| object_type.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(ids, BTreeSet::from([first.id(), second.id()]));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_obtain_matches_names_exactly() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let chore_list = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "Chore List",
+                [RelationSpec::text("Chore List Relation")],
+            ))
+            .await
+            .unwrap();
+
+        let chore = space
+            .obtain_object_type(&ObjectTypeSpec::new("Chore", []))
+            .await
+            .unwrap();
+        assert_ne!(chore.id(), chore_list.id());
+        assert_eq!(chore.name(), "Chore");
+
+        let by_name = space.get_object_types_by_name("chore").await.unwrap();
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].id(), chore.id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_has_no_templates_until_one_is_made() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("TestObjectType", []))
+            .await
+            .unwrap();
+        assert!(object_type.templates(&space).await.unwrap().is_empty());
+        assert!(object_type
+            .default_template(&space)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Without a default template there's nothing to apply, which isn't an error
+        let mut description =
+            ObjectSpec::new(object_type.clone(), "Without Template").as_description();
+        description.use_default_template = true;
+        let created = space.create_object(description).await.unwrap();
+        assert_eq!(created.name(), "Without Template");
+
+        let object = space
+            .obtain_object(&ObjectSpec::new(object_type, "Not A Template"))
+            .await
+            .unwrap();
+        let error = space
+            .create_object_from_template(&object, "From Template")
+            .await
+            .unwrap_err();
+        assert_eq!(error.message(), "Object `Not A Template` is not a template");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_lists_the_templates_made_for_it() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .obtain_relation(&RelationSpec::text("Template Text"))
+            .await
+            .unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Templated", [relation.as_spec()]))
+            .await
+            .unwrap();
+
+        let template = object_type
+            .create_template(&space, "Prefilled")
+            .await
+            .unwrap();
+        template
+            .set(
+                &relation,
+                RelationValue::Text("From the template".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let templates = object_type.templates(&space).await.unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].id(), template.id());
+
+        let created = space
+            .create_object_from_template(&templates[0], "From Template")
+            .await
+            .unwrap();
+        assert_eq!(created.name(), "From Template");
+        assert_relations_eq!(
+            created.get(&relation).await.unwrap(),
+            RelationValue::Text("From the template".to_string())
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_applies_its_default_template_on_create() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .obtain_relation(&RelationSpec::text("Template Text"))
+            .await
+            .unwrap();
+        let spec = ObjectTypeSpec::new("Templated", [relation.as_spec()]);
+        let object_type = space.obtain_object_type(&spec).await.unwrap();
+
+        let template = object_type
+            .create_template(&space, "Default")
+            .await
+            .unwrap();
+        template
+            .set(
+                &relation,
+                RelationValue::Text("From the default".to_string()),
+            )
+            .await
+            .unwrap();
+        object_type
+            .set_default_template(&space, &template)
+            .await
+            .unwrap();
+
+        let object_type = space.obtain_object_type(&spec).await.unwrap();
+        assert_eq!(
+            object_type
+                .default_template(&space)
+                .await
+                .unwrap()
+                .map(|template| template.id()),
+            Some(template.id())
+        );
+
+        let mut description =
+            ObjectSpec::new(object_type.clone(), "With Template").as_description();
+        description.use_default_template = true;
+        let created = space.create_object(description).await.unwrap();
+        assert_eq!(created.name(), "With Template");
+        assert_relations_eq!(
+            created.get(&relation).await.unwrap(),
+            RelationValue::Text("From the default".to_string())
+        );
+
+        // Opting out leaves the object empty even though the type has a default template
+        let created = space
+            .create_object(ObjectSpec::new(object_type, "Without Template").as_description())
+            .await
+            .unwrap();
+        assert!(created.get(&relation).await.is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_can_get_bundled_types_by_key() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page = space.bundled_type(BundledType::Page).await.unwrap();
+        assert_eq!(page.name(), "Page");
+        assert_eq!(page.to_string(), "Page");
+
+        let task = space.bundled_type(BundledType::Task).await.unwrap();
+        assert_eq!(task.name(), "Task");
+        assert_ne!(page.id(), task.id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_can_be_created_with_many_relations_under_a_concurrency_limit() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .with_max_concurrent_requests(NonZeroUsize::new(2).unwrap())
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relations = (0..40)
+            .map(|index| RelationSpec::text(format!("Relation {index}")))
+            .collect::<BTreeSet<_>>();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "ManyRelations",
+                relations.iter().cloned(),
+            ))
+            .await
+            .unwrap();
+
+        let created = object_type
+            .recommended_relations()
+            .iter()
+            .map(|relation| relation.as_spec())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(created, relations);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_can_be_created_with_an_icon_and_color() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let spec = ObjectTypeSpec {
+            icon: Some("📚".to_string()),
+            color: Some(3),
+            ..ObjectTypeSpec::new("Book", [])
+        };
+        let created = space.create_object_type(&spec).await.unwrap();
+        assert_eq!(created.icon(), Some("📚"));
+        assert_eq!(created.color(), Some(3));
+
+        // Icon and color don't take part in matching an existing type
+        let found = space
+            .get_object_type(&ObjectTypeSpec::new("Book", []))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id(), created.id());
+        assert_eq!(found.icon(), Some("📚"));
+        assert_eq!(found.color(), Some(3));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_without_recommended_relations_is_found() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let spec = ObjectTypeSpec::new("Bare", []);
+        let created = space.obtain_object_type(&spec).await.unwrap();
+
+        let found = space.get_object_type(&spec).await.unwrap().unwrap();
+        assert_eq!(found.id(), created.id());
+        assert!(found.recommended_relations().is_empty());
+
+        let by_name = space.get_object_types_by_name("Bare").await.unwrap();
+        assert_eq!(by_name.len(), 1);
+        assert_eq!(by_name[0].id(), created.id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_exposes_its_relation_groups() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let object_type = space
+            .obtain_object_type(
+                &ObjectTypeSpec::builder("Grouped")
+                    .relation(RelationSpec::url("Source"))
+                    .relation(RelationSpec::text("Author"))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let groups = object_type.relation_groups(&space).await.unwrap();
+        let (_, sidebar) = groups
+            .iter()
+            .find(|(group, _)| *group == RelationGroup::Sidebar)
+            .unwrap();
+        assert_eq!(
+            sidebar
+                .iter()
+                .map(|relation| relation.as_spec())
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([RelationSpec::url("Source"), RelationSpec::text("Author")])
+        );
+        assert!(groups.iter().all(|(_, relations)| !relations.is_empty()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn object_type_names_match_regardless_of_case() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let bookmark = space.bundled_type(BundledType::Bookmark).await.unwrap();
+        let found = space
+            .get_object_type(&ObjectTypeSpec::new(
+                bookmark.name().to_lowercase(),
+                bookmark.recommended_relations().iter().map(|r| r.as_spec()),
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id(), bookmark.id());
+
+        let created = space
+            .obtain_object_type(&ObjectTypeSpec::new(
+                "Reading List",
+                [RelationSpec::url("Link")],
+            ))
+            .await
+            .unwrap();
+        let (obtained, existence) = space
+            .obtain_object_type_with_existence(&ObjectTypeSpec::new(
+                "reading LIST",
+                [RelationSpec::url("Link")],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(existence, Existence::Found);
+        assert_eq!(obtained.id(), created.id());
+        assert_eq!(obtained.name(), "Reading List");
+    })
+    .await;
+}
+
+#[test]
+fn object_type_spec_can_be_built_incrementally() {
+    let with_due_date = true;
+
+    let mut builder = ObjectTypeSpec::builder("Article")
+        .relation(RelationSpec::url("Source"))
+        .relations([RelationSpec::text("Author"), RelationSpec::number("Pages")])
+        .icon("📰")
+        .color(3);
+    if with_due_date {
+        builder = builder.relation(RelationSpec::date("Due"));
+    }
+    let spec = builder.build();
+
+    assert_eq!(spec.name, "Article");
+    assert_eq!(
+        spec.recommended_relations,
+        BTreeSet::from([
+            RelationSpec::url("Source"),
+            RelationSpec::text("Author"),
+            RelationSpec::number("Pages"),
+            RelationSpec::date("Due"),
+        ])
+    );
+    assert_eq!(spec.icon.as_deref(), Some("📰"));
+    assert_eq!(spec.color, Some(3));
+}
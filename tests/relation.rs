@@ -1,8 +1,12 @@
 mod utils;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
-use anytype_friend::{AnytypeClient, NetworkSync, ObjectTypeSpec, RelationFormat, RelationSpec};
+use anytype_friend::{
+    AnytypeClient, BundledType, CoerceError, EmptyName, Existence, NetworkSync, ObjectDescription,
+    ObjectLayout, ObjectSpec, ObjectTypeSpec, ParseRelationFormatError, RelationDescription,
+    RelationFormat, RelationSpec, RelationValue,
+};
 use utils::run_with_service;
 
 #[tokio::test]
@@ -104,6 +108,37 @@ async fn relation_can_obtain_a_new_one() {
     .await;
 }
 
+#[tokio::test]
+async fn relation_obtain_reports_whether_it_was_created() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let spec = RelationSpec {
+            name: "Longitude".to_string(),
+            format: RelationFormat::Number,
+        };
+
+        let (created, existence) = space.obtain_relation_with_existence(&spec).await.unwrap();
+        assert_eq!(existence, Existence::Created);
+
+        let (found, existence) = space.obtain_relation_with_existence(&spec).await.unwrap();
+        assert_eq!(existence, Existence::Found);
+        assert_eq!(created.id(), found.id());
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn relation_can_create_object_format_with_types() {
     let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
@@ -124,6 +159,7 @@ async fn relation_can_create_object_format_with_types() {
             .obtain_object_type(&ObjectTypeSpec {
                 name: "TestObjectType".to_string(),
                 recommended_relations: BTreeSet::new(),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -148,3 +184,559 @@ async fn relation_can_create_object_format_with_types() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn relation_can_create_one_with_description_and_max_count() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .create_relation(RelationDescription {
+                description: Some("The main author of a work".to_string()),
+                max_count: Some(1),
+                ..RelationSpec::object("Primary Author", []).as_description()
+            })
+            .await
+            .unwrap();
+        assert_eq!(relation.description(), Some("The main author of a work"));
+        assert_eq!(relation.max_count(), Some(1));
+
+        let relation = space
+            .get_relation(&relation.as_spec())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(relation.description(), Some("The main author of a work"));
+        assert_eq!(relation.max_count(), Some(1));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_can_list_options_of_a_select_relation() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        // Bundled relations can come with options of their own
+        let relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Test Status".to_string(),
+                format: RelationFormat::Select,
+            })
+            .await
+            .unwrap();
+
+        let other_relation = space
+            .obtain_relation(&RelationSpec::select("Test Priority"))
+            .await
+            .unwrap();
+
+        let todo = space
+            .create_relation_option(&relation, "Todo", "red")
+            .await
+            .unwrap();
+        let done = space
+            .create_relation_option(&relation, "Done", "teal")
+            .await
+            .unwrap();
+        let high = space
+            .create_relation_option(&other_relation, "High", "red")
+            .await
+            .unwrap();
+        assert_eq!(todo.text(), "Todo");
+        assert_eq!(todo.color(), "red");
+        assert_eq!(todo.relation_key(), done.relation_key());
+        assert_ne!(todo.relation_key(), high.relation_key());
+
+        let options = space.get_relation_options(&relation).await.unwrap();
+        assert_eq!(
+            options
+                .iter()
+                .map(|option| (option.id(), option.text(), option.color()))
+                .collect::<BTreeSet<_>>(),
+            BTreeSet::from([(todo.id(), "Todo", "red"), (done.id(), "Done", "teal")])
+        );
+        assert!(options
+            .iter()
+            .all(|option| option.relation_key() == todo.relation_key()));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_object_format_is_independent_of_type_order() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let mut ids = Vec::new();
+        for name in ["FirstType", "SecondType", "ThirdType"] {
+            let object_type = space
+                .obtain_object_type(&ObjectTypeSpec {
+                    name: name.to_string(),
+                    recommended_relations: BTreeSet::new(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            ids.push(object_type.id());
+        }
+
+        let forwards = RelationSpec {
+            name: "TestRelation".to_string(),
+            format: RelationFormat::Object {
+                types: ids.iter().copied().collect(),
+            },
+        };
+        let backwards = RelationSpec {
+            name: "TestRelation".to_string(),
+            format: RelationFormat::Object {
+                types: ids.iter().rev().copied().collect(),
+            },
+        };
+
+        fn hash(spec: &RelationSpec) -> u64 {
+            use std::hash::{Hash, Hasher};
+
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            spec.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(forwards, backwards);
+        assert_eq!(forwards.cmp(&backwards), std::cmp::Ordering::Equal);
+        assert_eq!(hash(&forwards), hash(&backwards));
+        assert_eq!(BTreeSet::from([forwards, backwards]).len(), 1);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_can_change_between_compatible_formats() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .obtain_relation(&RelationSpec {
+                name: "Homepage".to_string(),
+                format: RelationFormat::Text,
+            })
+            .await
+            .unwrap();
+
+        let relation = relation
+            .change_format(&space, RelationFormat::Url)
+            .await
+            .unwrap();
+        assert_eq!(*relation.format(), RelationFormat::Url);
+
+        let fetched = space
+            .get_relation(&RelationSpec {
+                name: "Homepage".to_string(),
+                format: RelationFormat::Url,
+            })
+            .await
+            .unwrap();
+        assert!(fetched.is_some());
+
+        let error = relation
+            .change_format(&space, RelationFormat::Number)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.message(),
+            "Changing a relation's format from Url to Number is not supported"
+        );
+    })
+    .await;
+}
+
+#[test]
+fn relation_format_can_be_parsed_from_its_name() {
+    assert_eq!("Url".parse(), Ok(RelationFormat::Url));
+    assert_eq!("number".parse(), Ok(RelationFormat::Number));
+    assert_eq!("MULTISELECT".parse(), Ok(RelationFormat::MultiSelect));
+    assert_eq!(
+        "Object".parse::<RelationFormat>(),
+        Err(ParseRelationFormatError::MissingObjectTypes)
+    );
+    assert_eq!(
+        "Markdown".parse::<RelationFormat>(),
+        Err(ParseRelationFormatError::Unknown("Markdown".to_string()))
+    );
+}
+
+#[test]
+fn relation_value_can_be_coerced_from_a_string() {
+    assert!(matches!(
+        RelationValue::coerce_to(&RelationFormat::Number, " 4.5 "),
+        Ok(RelationValue::Number(number)) if number == 4.5
+    ));
+    assert!(matches!(
+        RelationValue::coerce_to(&RelationFormat::Checkbox, "Yes"),
+        Ok(RelationValue::Checkbox(true))
+    ));
+    assert!(matches!(
+        RelationValue::coerce_to(&RelationFormat::Url, "https://anytype.io"),
+        Ok(RelationValue::Url(url)) if url == "https://anytype.io"
+    ));
+
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+    for raw in ["2024-05-01", "2024-05-01 00:00:00", "2024-05-01T00:00:00Z"] {
+        assert!(matches!(
+            RelationValue::coerce_to(&RelationFormat::Date, raw),
+            Ok(RelationValue::DateUtc(parsed)) if parsed == date
+        ));
+    }
+
+    assert_eq!(
+        RelationValue::coerce_to(&RelationFormat::Number, "four").unwrap_err(),
+        CoerceError::Invalid {
+            format: RelationFormat::Number,
+            raw: "four".to_string()
+        }
+    );
+    assert_eq!(
+        RelationValue::coerce_to(&RelationFormat::Select, "Done").unwrap_err(),
+        CoerceError::Unsupported(RelationFormat::Select)
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn relation_value_still_accepts_naive_dates() {
+    let date = chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+        .unwrap()
+        .and_hms_opt(12, 30, 0)
+        .unwrap();
+    assert_eq!(RelationValue::Date(date).format(), RelationFormat::Date);
+    assert_eq!(
+        RelationValue::DateUtc(date.and_utc()).format(),
+        RelationFormat::Date
+    );
+
+    assert_eq!(
+        RelationValue::Date(date).as_datetime(),
+        Some(date.and_utc())
+    );
+    assert_eq!(
+        RelationValue::DateUtc(date.and_utc()).as_datetime(),
+        Some(date.and_utc())
+    );
+    assert_relations_eq!(
+        RelationValue::Date(date),
+        RelationValue::DateUtc(date.and_utc())
+    );
+}
+
+#[tokio::test]
+async fn relation_fails_to_get_with_an_empty_name() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let error = space
+            .get_relation(&RelationSpec::text(" "))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert_eq!(error.message(), "Can't search by an empty name");
+        assert!(EmptyName::from_status(&error).is_some());
+
+        let error = space
+            .obtain_object_type(&ObjectTypeSpec::new("", []))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert!(EmptyName::from_status(&error).is_some());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_can_get_bundled_ones_by_key() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let description = space
+            .bundled_relation("description")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(description.name(), "Description");
+        assert_eq!(description.format(), &RelationFormat::Text);
+        assert_eq!(description.to_string(), "Description (Text)");
+
+        assert!(space
+            .bundled_relation("notABundledRelation")
+            .await
+            .unwrap()
+            .is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_can_list_the_types_using_it() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .obtain_relation(&RelationSpec::text("Shared Relation"))
+            .await
+            .unwrap();
+        assert!(relation.used_by_types(&space).await.unwrap().is_empty());
+
+        let first = space
+            .obtain_object_type(&ObjectTypeSpec::new("FirstType", [relation.as_spec()]))
+            .await
+            .unwrap();
+        let second = space
+            .obtain_object_type(&ObjectTypeSpec::new("SecondType", [relation.as_spec()]))
+            .await
+            .unwrap();
+        space
+            .obtain_object_type(&ObjectTypeSpec::new("UnrelatedType", []))
+            .await
+            .unwrap();
+
+        let types = relation
+            .used_by_types(&space)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object_type| object_type.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(types, BTreeSet::from([first.id(), second.id()]));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_can_create_a_date_one_that_includes_time() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let relation = space
+            .create_relation(RelationDescription {
+                include_time: true,
+                ..RelationSpec::date("Due At").as_description()
+            })
+            .await
+            .unwrap();
+        assert!(relation.include_time());
+
+        let relation = space
+            .get_relation(&relation.as_spec())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(relation.include_time());
+
+        let date_only = space
+            .obtain_relation(&RelationSpec::date("Due On"))
+            .await
+            .unwrap();
+        assert!(!date_only.include_time());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn relation_duplicates_can_be_merged() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let spec = RelationSpec::text("Raced Relation");
+        // What two racing obtain_relation calls end up doing, with a type made in between that
+        // references whichever of the two came first
+        let first = space.create_relation(spec.as_description()).await.unwrap();
+        let object_type = space
+            .obtain_object_type(&ObjectTypeSpec::new("Raced Type", [spec.clone()]))
+            .await
+            .unwrap();
+        let second = space.create_relation(spec.as_description()).await.unwrap();
+        assert!(space.get_relation(&spec).await.is_err());
+
+        let (canonical, duplicate) = if first.id() < second.id() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let object = space
+            .create_object(ObjectDescription {
+                relations: HashMap::from([(
+                    duplicate.clone(),
+                    RelationValue::Text("Kept".to_string()),
+                )]),
+                ..ObjectSpec::new(page_type, "Holds A Duplicate").as_description()
+            })
+            .await
+            .unwrap();
+        // Notes are outside of the layouts searches return by default
+        let note_type = space.bundled_type(BundledType::Note).await.unwrap();
+        let note = space
+            .create_object(ObjectDescription {
+                relations: HashMap::from([(
+                    duplicate.clone(),
+                    RelationValue::Text("Noted".to_string()),
+                )]),
+                ..ObjectSpec::new(note_type, "Note Holding A Duplicate").as_description()
+            })
+            .await
+            .unwrap();
+
+        let merged = space.deduplicate_relation("Raced Relation").await.unwrap();
+        assert_eq!(merged.id(), canonical.id());
+        assert_eq!(
+            space.get_relation(&spec).await.unwrap().unwrap().id(),
+            canonical.id()
+        );
+
+        let object = space
+            .get_objects_by_id([object.id()])
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_relations_eq!(
+            object.get(&canonical).await.unwrap(),
+            RelationValue::Text("Kept".to_string())
+        );
+        assert!(object.get(&duplicate).await.is_none());
+
+        let note = space
+            .list_objects_with_layouts(&[ObjectLayout::Note])
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|object| object.id() == note.id())
+            .unwrap();
+        assert_relations_eq!(
+            note.get(&canonical).await.unwrap(),
+            RelationValue::Text("Noted".to_string())
+        );
+
+        let object_type = space
+            .get_object_types_by_name("Raced Type")
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|found| found.id() == object_type.id())
+            .unwrap();
+        let recommended = object_type
+            .recommended_relations()
+            .iter()
+            .map(|relation| relation.id())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(recommended, BTreeSet::from([canonical.id()]));
+
+        // Running it again on the merged relation changes nothing
+        let merged = space.deduplicate_relation("Raced Relation").await.unwrap();
+        assert_eq!(merged.id(), canonical.id());
+    })
+    .await;
+}
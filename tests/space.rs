@@ -0,0 +1,168 @@
+mod utils;
+
+use anytype_friend::{AnytypeClient, BundledType, NetworkSync, ObjectSpec, Permission, RawValue};
+use utils::run_with_service;
+
+#[tokio::test]
+async fn space_lists_its_owner_as_a_member() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let members = space.members().await.unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].identity(), client.account().id);
+        assert_eq!(members[0].permission(), Permission::Owner);
+
+        assert_eq!(space.my_permission().await.unwrap(), Permission::Owner);
+
+        let owner = space.member(&client.account().id).await.unwrap().unwrap();
+        assert_eq!(owner.id(), members[0].id());
+        assert_eq!(owner.identity(), client.account().id);
+        assert!(space.member("not an identity").await.unwrap().is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_fails_to_generate_invite_without_network_sync() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let error = space.generate_invite().await.unwrap_err();
+        assert_eq!(
+            error.message(),
+            "Sharing a space requires the client to use NetworkSync::Sync"
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_exposes_its_info() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let info = space.info();
+        assert_eq!(
+            info.space_id(),
+            client.account().info.as_ref().unwrap().account_space_id
+        );
+        assert!(!info.profile_object_id().is_empty());
+        // anytype-heart serves its gateway over plain http on the loopback interface
+        let address = info.gateway_url().strip_prefix("http://").unwrap();
+        let (host, port) = address.split_once(':').unwrap();
+        assert_eq!(host, "127.0.0.1");
+        port.parse::<u16>().unwrap();
+
+        space.refresh().await.unwrap();
+        let refreshed = space.info();
+        assert_eq!(refreshed.space_id(), info.space_id());
+        assert_eq!(refreshed.profile_object_id(), info.profile_object_id());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_is_not_opened_by_an_unknown_name() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        assert!(client
+            .open_space_by_name("No Such Space")
+            .await
+            .unwrap()
+            .is_none());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn space_opened_read_only_refuses_changes() {
+    let temp_dir = tempdir::TempDir::new("anytype-friend").unwrap();
+    let temp_dir_path = temp_dir.path();
+
+    run_with_service(|port| async move {
+        let (_, client) = AnytypeClient::connect(&format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap()
+            .with_network_sync(NetworkSync::NoSync)
+            .with_root_path(temp_dir_path)
+            .create_account("Test Client")
+            .await
+            .unwrap();
+
+        let space = client.default_space().await.unwrap().unwrap();
+        let page_type = space.bundled_type(BundledType::Page).await.unwrap();
+        let spec = ObjectSpec::new(page_type, "Report");
+        let object = space.create_object(spec.as_description()).await.unwrap();
+
+        let read_only = client
+            .open_space_readonly(space.info().space_id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!space.is_read_only());
+        assert!(read_only.is_read_only());
+
+        let error = read_only
+            .create_object(spec.as_description())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+
+        let objects = read_only.get_objects_by_id([object.id()]).await.unwrap();
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].name(), "Report");
+
+        let error = objects[0]
+            .set_raw_detail("name", RawValue::String("Renamed".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), tonic::Code::PermissionDenied);
+        assert_eq!(objects[0].name(), "Report");
+    })
+    .await;
+}
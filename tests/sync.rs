@@ -55,6 +55,7 @@ async fn can_sync() {
                         format: RelationFormat::Url,
                     },
                 ]),
+                ..Default::default()
             })
             .await
             .unwrap();
@@ -126,18 +127,18 @@ async fn can_sync() {
                         format: RelationFormat::Url,
                     },
                 ]),
+                ..Default::default()
             })
             .await
             .unwrap();
 
         let object = space
             .create_object(ObjectDescription {
-                ty: object_type.clone(),
-                name: "Test Object".to_string(),
                 relations: HashMap::from([(
                     description_relation.clone(),
                     RelationValue::Text("We can create objects!".to_string()),
                 )]),
+                ..ObjectSpec::new(object_type.clone(), "Test Object").as_description()
             })
             .await
             .unwrap();
@@ -1,11 +1,13 @@
 use std::{
     env,
     future::Future,
-    io::ErrorKind,
+    io::{self, ErrorKind},
     process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
 };
 
 use rand::Rng;
+use tracing::subscriber::DefaultGuard;
 
 const MACOS_PATH: &str =
     "/Applications/Anytype.app/Contents/Resources/app.asar.unpacked/dist/anytypeHelper";
@@ -53,6 +55,68 @@ where
     callback(port).await
 }
 
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Warnings emitted through `tracing` on the current thread for as long as this is alive
+///
+/// `#[tokio::test]` runs everything, including tasks spawned by the crate, on the test's thread
+/// so this sees all of the crate's diagnostics
+#[allow(dead_code)]
+pub struct CapturedLogs {
+    buffer: SharedBuffer,
+    _guard: DefaultGuard,
+}
+
+#[allow(dead_code)]
+impl CapturedLogs {
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.buffer.0.lock().unwrap()).into_owned()
+    }
+
+    pub fn contains(&self, message: &str) -> bool {
+        self.contents().contains(message)
+    }
+}
+
+/// Start capturing the crate's `tracing` output so tests can assert on it
+///
+/// Set `ANYTYPE_PRINT_LOGS` to also print the captured output when the test finishes
+#[allow(dead_code)]
+pub fn capture_logs() -> CapturedLogs {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::WARN)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    CapturedLogs {
+        buffer,
+        _guard: tracing::subscriber::set_default(subscriber),
+    }
+}
+
+impl Drop for CapturedLogs {
+    fn drop(&mut self) {
+        if env::var("ANYTYPE_PRINT_LOGS").is_ok() {
+            eprint!("{}", self.contents());
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! assert_relations_eq {
     ($a:expr, $b:expr) => {
@@ -62,16 +126,22 @@ macro_rules! assert_relations_eq {
             | (RelationValue::Email(a), RelationValue::Email(b))
             | (RelationValue::Phone(a), RelationValue::Phone(b)) => a == b,
             (RelationValue::Number(a), RelationValue::Number(b)) => a == b,
+            (RelationValue::DateUtc(a), RelationValue::DateUtc(b)) => a == b,
+            // The naive variant is taken to be in UTC, so it equals the same date in UTC
+            #[allow(deprecated)]
             (RelationValue::Date(a), RelationValue::Date(b)) => a == b,
+            #[allow(deprecated)]
+            (RelationValue::Date(a), RelationValue::DateUtc(b))
+            | (RelationValue::DateUtc(b), RelationValue::Date(a)) => a.and_utc() == b,
             (RelationValue::Checkbox(a), RelationValue::Checkbox(b)) => a == b,
+            // Object relations are ordered lists, so the order of the objects matters too
             (RelationValue::Object(a), RelationValue::Object(b)) => {
-                use ::std::collections::HashSet;
                 a.into_iter()
                     .map(|object| object.id().clone())
-                    .collect::<HashSet<_>>()
+                    .collect::<Vec<_>>()
                     == b.into_iter()
                         .map(|object| object.id().clone())
-                        .collect::<HashSet<_>>()
+                        .collect::<Vec<_>>()
             }
             _ => false,
         };